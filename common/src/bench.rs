@@ -0,0 +1,146 @@
+// A small benchmark harness in the spirit of Criterion. Each stage is run once to warm up, then
+// sampled until its time budget runs out (within a minimum and maximum number of samples), and is
+// summarised by its fastest, median and mean sample.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const MIN_SAMPLES: usize = 3;
+const MAX_SAMPLES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub stage: String,
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Timing {
+    fn new(stage: &str, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        Self {
+            stage: stage.to_string(),
+            samples: samples.len(),
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        }
+    }
+}
+
+pub struct Bencher {
+    budget: Duration,
+    timings: Vec<Timing>,
+}
+
+impl Bencher {
+    // `budget` is roughly how long to spend sampling each stage
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            timings: Vec::new(),
+        }
+    }
+
+    // Times `f`, returning what it returned so later stages can use it
+    pub fn run<T>(&mut self, stage: &str, mut f: impl FnMut() -> T) -> T {
+        self.run_with_setup(stage, || (), |()| f())
+    }
+
+    // Times `f` on a fresh input from `setup` each sample, without timing `setup`
+    pub fn run_with_setup<I, T>(
+        &mut self,
+        stage: &str,
+        mut setup: impl FnMut() -> I,
+        mut f: impl FnMut(I) -> T,
+    ) -> T {
+        let mut result = black_box(f(setup()));
+        let mut samples = Vec::new();
+        let started = Instant::now();
+
+        while samples.len() < MIN_SAMPLES
+            || (samples.len() < MAX_SAMPLES && started.elapsed() < self.budget)
+        {
+            let input = setup();
+            let start = Instant::now();
+            let output = black_box(f(black_box(input)));
+            samples.push(start.elapsed());
+
+            result = output;
+        }
+
+        self.timings.push(Timing::new(stage, samples));
+
+        return result;
+    }
+
+    pub fn timings(&self) -> &[Timing] {
+        &self.timings
+    }
+}
+
+// Durations to three significant figures in the most readable unit
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos() as f64;
+
+    let (value, unit) = match ns {
+        n if n < 1e3 => (n, "ns"),
+        n if n < 1e6 => (n / 1e3, "µs"),
+        n if n < 1e9 => (n / 1e6, "ms"),
+        n => (n / 1e9, "s"),
+    };
+
+    match value {
+        v if v < 10.0 => format!("{:.2}{}", v, unit),
+        v if v < 100.0 => format!("{:.1}{}", v, unit),
+        v => format!("{:.0}{}", v, unit),
+    }
+}
+
+#[test]
+fn test_bencher() {
+    let mut b = Bencher::new(Duration::from_millis(5));
+    let mut calls = 0;
+
+    let parsed = b.run("parse", || {
+        calls += 1;
+        vec![3, 1, 2]
+    });
+    let sorted = b.run_with_setup(
+        "solve",
+        || parsed.clone(),
+        |mut v| {
+            v.sort();
+            v
+        },
+    );
+
+    assert_eq!(sorted, vec![1, 2, 3]);
+    assert!(calls > MIN_SAMPLES);
+    assert_eq!(b.timings().len(), 2);
+    assert_eq!(b.timings()[0].stage, "parse");
+    assert_eq!(b.timings()[0].samples, calls - 1);
+
+    let t = &b.timings()[1];
+    assert!(t.samples >= MIN_SAMPLES && t.samples <= MAX_SAMPLES);
+    assert!(t.min <= t.median);
+}
+
+#[test]
+fn test_timing_summary() {
+    let ms = Duration::from_millis;
+    let t = Timing::new("x", vec![ms(4), ms(1), ms(10)]);
+
+    assert_eq!((t.min, t.median, t.mean), (ms(1), ms(4), ms(5)));
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+    assert_eq!(format_duration(Duration::from_nanos(1_234)), "1.23µs");
+    assert_eq!(format_duration(Duration::from_micros(45_600)), "45.6ms");
+    assert_eq!(format_duration(Duration::from_secs(3)), "3.00s");
+}
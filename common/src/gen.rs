@@ -0,0 +1,181 @@
+// Building blocks for the synthetic input generators. Generators have to be reproducible from a
+// seed, so they get their own small PRNG rather than anything seeded from the system.
+
+use std::collections::BTreeMap;
+
+// xorshift64*, seeded through splitmix64 so that small or zero seeds still give good output.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        // The state must never be zero
+        Self(z.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // A value in lo..=hi
+    pub fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+
+        match (hi - lo).checked_add(1) {
+            None => self.next_u64(),
+            Some(span) => lo + self.next_u64() % span,
+        }
+    }
+
+    // True with a probability of `percent` in 100
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.range(0, 99) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0, i as u64) as usize);
+        }
+    }
+
+    // `count` distinct values from lo..=hi, in random order
+    pub fn sample(&mut self, lo: u64, hi: u64, count: usize) -> Vec<u64> {
+        let mut values = (lo..=hi).collect::<Vec<_>>();
+        assert!(
+            count <= values.len(),
+            "can't pick {} from {}..={}",
+            count,
+            lo,
+            hi
+        );
+
+        self.shuffle(&mut values);
+        values.truncate(count);
+
+        return values;
+    }
+}
+
+// A size parameter a generator accepts, given on the command line as `--name value`.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: u64,
+    pub help: &'static str,
+}
+
+// The size parameters for one generator run, with every parameter filled in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sizes(BTreeMap<&'static str, u64>);
+
+impl Sizes {
+    pub fn defaults(params: &[Param]) -> Self {
+        Self(params.iter().map(|p| (p.name, p.default)).collect())
+    }
+
+    pub fn from_args(params: &[Param], args: &[String]) -> Result<Self, String> {
+        let mut sizes = Self::defaults(params);
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let param = arg
+                .strip_prefix("--")
+                .and_then(|name| params.iter().find(|p| p.name == name))
+                .ok_or_else(|| format!("unknown size parameter {}", arg))?;
+
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for {}", arg))?;
+            let value = value
+                .parse()
+                .map_err(|_| format!("{} must be a number, got {}", arg, value))?;
+
+            sizes.0.insert(param.name, value);
+        }
+
+        return Ok(sizes);
+    }
+
+    pub fn with(mut self, name: &'static str, value: u64) -> Self {
+        self.0.insert(name, value);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> u64 {
+        *self
+            .0
+            .get(name)
+            .unwrap_or_else(|| panic!("no size parameter called {}", name))
+    }
+}
+
+#[allow(dead_code)]
+const PARAMS: &[Param] = &[
+    Param {
+        name: "lines",
+        default: 10,
+        help: "number of lines",
+    },
+    Param {
+        name: "width",
+        default: 5,
+        help: "characters per line",
+    },
+];
+
+#[test]
+fn test_rng_is_reproducible() {
+    let a = (0..10).map(|_| Rng::new(7).next_u64()).collect::<Vec<_>>();
+    assert!(a.iter().all(|v| *v == a[0]));
+
+    let mut rng = Rng::new(7);
+    let b = (0..10).map(|_| rng.next_u64()).collect::<Vec<_>>();
+    let mut rng = Rng::new(7);
+    let c = (0..10).map(|_| rng.next_u64()).collect::<Vec<_>>();
+    assert_eq!(b, c);
+
+    assert_ne!(Rng::new(0).next_u64(), Rng::new(1).next_u64());
+}
+
+#[test]
+fn test_rng_bounds() {
+    let mut rng = Rng::new(0);
+
+    for _ in 0..1000 {
+        let v = rng.range(3, 9);
+        assert!((3..=9).contains(&v));
+    }
+    assert_eq!(rng.range(4, 4), 4);
+    rng.range(0, u64::MAX);
+
+    let mut sample = rng.sample(1, 20, 20);
+    sample.sort();
+    assert_eq!(sample, (1..=20).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_sizes_from_args() {
+    let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+    let sizes = Sizes::from_args(PARAMS, &args("--width 80")).unwrap();
+    assert_eq!(sizes.get("lines"), 10);
+    assert_eq!(sizes.get("width"), 80);
+    assert_eq!(sizes, Sizes::defaults(PARAMS).with("width", 80));
+
+    assert!(Sizes::from_args(PARAMS, &args("--height 3")).is_err());
+    assert!(Sizes::from_args(PARAMS, &args("--width")).is_err());
+    assert!(Sizes::from_args(PARAMS, &args("--width wide")).is_err());
+    assert!(Sizes::from_args(PARAMS, &args("width 3")).is_err());
+}
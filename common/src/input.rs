@@ -0,0 +1,169 @@
+// Where a puzzle input comes from. On the command line an input is a file path, `-` for stdin, or
+// the text itself after `--text`. Solvers only ever see the text.
+
+use std::io::{BufRead, BufReader, Cursor, Read};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Path(String),
+    Stdin,
+    Text(String),
+}
+
+impl Source {
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Self::Stdin,
+            path => Self::Path(path.to_string()),
+        }
+    }
+
+    // Every argument is an input, except that `--text` takes the next argument as the input itself
+    pub fn from_args(args: &[String]) -> Result<Vec<Self>, String> {
+        let mut sources = Vec::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            sources.push(match arg.as_str() {
+                "--text" => Self::Text(
+                    args.next()
+                        .ok_or_else(|| String::from("missing value for --text"))?
+                        .clone(),
+                ),
+                arg => Self::from_arg(arg),
+            });
+        }
+
+        if sources.iter().filter(|s| **s == Self::Stdin).count() > 1 {
+            return Err(String::from("stdin can only be read once"));
+        }
+
+        return Ok(sources);
+    }
+
+    // How to refer to the input in output
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Path(path) => path,
+            Self::Stdin => "stdin",
+            Self::Text(_) => "text",
+        }
+    }
+
+    pub fn reader(&self) -> Result<Box<dyn BufRead>, String> {
+        match self {
+            Self::Path(path) => std::fs::File::open(path)
+                .map(|f| Box::new(BufReader::new(f)) as Box<dyn BufRead>)
+                .map_err(|e| format!("{}: {}", path, e)),
+            Self::Stdin => Ok(Box::new(std::io::stdin().lock())),
+            Self::Text(text) => Ok(Box::new(Cursor::new(text.clone().into_bytes()))),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        let mut text = String::new();
+        self.reader()?
+            .read_to_string(&mut text)
+            .map_err(|e| format!("{}: {}", self.label(), e))?;
+
+        return Ok(text);
+    }
+}
+
+// Reads every input given in `args`, each with its label
+pub fn read_all(args: &[String]) -> Result<Vec<(String, String)>, String> {
+    Source::from_args(args)?
+        .iter()
+        .map(|source| Ok((source.label().to_string(), source.read()?)))
+        .collect()
+}
+
+// The arguments a day was run with, and whether `--big` was among them, which is taken out
+pub fn command_line() -> (bool, Vec<String>) {
+    split_big(std::env::args().skip(1).collect())
+}
+
+fn split_big(args: Vec<String>) -> (bool, Vec<String>) {
+    let big = args.iter().any(|arg| arg == "--big");
+    let args = args.into_iter().filter(|arg| arg != "--big").collect();
+
+    (big, args)
+}
+
+// Reads every input given in `args`, or says why it couldn't and exits
+pub fn read_all_or_exit(args: &[String]) -> Vec<(String, String)> {
+    read_all(args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
+
+// What a day with no options of its own needs from its command line
+pub fn command_line_inputs() -> (bool, Vec<(String, String)>) {
+    let (big, args) = command_line();
+
+    (big, read_all_or_exit(&args))
+}
+
+// Reads one of a day's own files, such as "example" or "input", from its crate directory so the
+// day can be run from anywhere.
+#[macro_export]
+macro_rules! day_file {
+    ($name:expr) => {
+        $crate::input::read_day_file(env!("CARGO_MANIFEST_DIR"), $name)
+    };
+}
+
+pub fn read_day_file(dir: &str, name: &str) -> String {
+    let path = std::path::Path::new(dir).join(name);
+
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+#[test]
+fn test_from_args() {
+    let args = |s: &[&str]| s.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        Source::from_args(&args(&["example", "-", "--text", "1 2\n3"])).unwrap(),
+        vec![
+            Source::Path(String::from("example")),
+            Source::Stdin,
+            Source::Text(String::from("1 2\n3"))
+        ]
+    );
+    assert!(Source::from_args(&args(&["--text"])).is_err());
+    assert!(Source::from_args(&args(&["-", "-"])).is_err());
+
+    assert_eq!(
+        read_all(&args(&["--text", "a"])),
+        Ok(vec![(String::from("text"), String::from("a"))])
+    );
+    assert!(read_all(&args(&["no such file"])).is_err());
+
+    assert_eq!(
+        split_big(args(&["--big", "example", "--big"])),
+        (true, args(&["example"]))
+    );
+    assert_eq!(split_big(args(&["-"])), (false, args(&["-"])));
+}
+
+#[test]
+fn test_read() {
+    assert_eq!(Source::Text(String::from("a\nb")).read().unwrap(), "a\nb");
+    assert_eq!(
+        Source::Text(String::from("a\nb"))
+            .reader()
+            .unwrap()
+            .lines()
+            .count(),
+        2
+    );
+    assert!(Source::Path(String::from("no such file"))
+        .read()
+        .unwrap_err()
+        .starts_with("no such file: "));
+
+    let manifest = Source::Path(format!("{}/Cargo.toml", env!("CARGO_MANIFEST_DIR")));
+    assert_eq!(manifest.read().unwrap(), day_file!("Cargo.toml"));
+}
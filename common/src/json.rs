@@ -0,0 +1,106 @@
+// Just enough JSON for the reports the days and the runner write, and for reading the runner's own
+// reports back, without pulling in a crate.
+
+use std::collections::BTreeMap;
+
+// A quoted JSON string, escaping everything RFC 8259 requires
+pub fn string(s: &str) -> String {
+    let mut out = String::from("\"");
+
+    s.chars().for_each(|c| match c {
+        '"' => out += "\\\"",
+        '\\' => out += "\\\\",
+        '\n' => out += "\\n",
+        '\r' => out += "\\r",
+        '\t' => out += "\\t",
+        c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+        c => out.push(c),
+    });
+
+    out + "\""
+}
+
+// Reads back a flat object such as `aoc verify` writes, with string, number and null values only.
+// Numbers are kept as they were written.
+pub fn parse_object(line: &str) -> Option<BTreeMap<String, Option<String>>> {
+    let mut chars = line.trim().strip_prefix('{')?.chars().peekable();
+    let mut fields = BTreeMap::new();
+
+    let read_string = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        let mut s = String::new();
+        loop {
+            match chars.next()? {
+                '"' => return Some(s),
+                '\\' => match chars.next()? {
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'u' => {
+                        let hex = (0..4).map(|_| chars.next()).collect::<Option<String>>()?;
+                        s.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    c => s.push(c),
+                },
+                c => s.push(c),
+            }
+        }
+    };
+
+    loop {
+        match chars.next()? {
+            '}' => return Some(fields),
+            ',' => continue,
+            '"' => (),
+            _ => return None,
+        }
+
+        let key = read_string(&mut chars)?;
+        if chars.next()? != ':' {
+            return None;
+        }
+
+        let value = match chars.peek()? {
+            '"' => {
+                chars.next();
+                Some(read_string(&mut chars)?)
+            }
+            _ => {
+                let mut raw = String::new();
+                while let Some(c) = chars.next_if(|c| *c != ',' && *c != '}') {
+                    raw.push(c);
+                }
+                match raw.as_str() {
+                    "null" => None,
+                    _ => Some(raw),
+                }
+            }
+        };
+
+        fields.insert(key, value);
+    }
+}
+
+#[test]
+fn test_string() {
+    assert_eq!(string("plain"), "\"plain\"");
+    assert_eq!(string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    assert_eq!(string("1\n2\r\t\u{1}"), "\"1\\n2\\r\\t\\u0001\"");
+    assert_eq!(string("§★"), "\"§★\"");
+}
+
+#[test]
+fn test_parse_object() {
+    let line = format!(
+        "{{\"day\":7,\"answer\":null,\"error\":{}}}",
+        string("a\"b\n\u{1}§")
+    );
+    let fields = parse_object(&line).unwrap();
+
+    assert_eq!(fields["day"].as_deref(), Some("7"));
+    assert_eq!(fields["answer"], None);
+    assert_eq!(fields["error"].as_deref(), Some("a\"b\n\u{1}§"));
+
+    assert_eq!(parse_object("{}"), Some(BTreeMap::new()));
+    assert_eq!(parse_object("not json"), None);
+    assert_eq!(parse_object("{\"unterminated"), None);
+}
@@ -0,0 +1,14 @@
+#![allow(clippy::needless_return)]
+
+// Code shared between the days.
+
+pub mod bench;
+pub mod gen;
+pub mod input;
+pub mod json;
+mod num;
+pub mod par;
+pub mod parse;
+
+pub use num::{answer, checked_product, checked_sum, BigUint, Num, Overflow};
+pub use parse::{Error, ParseError};
@@ -0,0 +1,365 @@
+// Answers on big generated inputs don't fit in machine integers, and wrapping silently in release
+// builds is worse than failing. Solvers are written against `Num`, which is either a fixed width
+// integer checked on every operation or an arbitrary precision `BigUint` which never overflows.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overflow {
+    // What was being calculated
+    pub context: String,
+}
+
+impl Overflow {
+    pub fn new(context: &str) -> Self {
+        Self {
+            context: context.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "arithmetic overflow in {} (rerun with --big for an exact answer)",
+            self.context
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+// Send and Sync so that solvers can split their work across threads
+pub trait Num: Sized + Clone + Ord + Send + Sync + fmt::Display + fmt::Debug {
+    fn from_u64(n: u64) -> Self;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    // 2 to the power of `exp`
+    fn checked_pow2(exp: u32) -> Option<Self>;
+
+    fn zero() -> Self {
+        Self::from_u64(0)
+    }
+
+    fn one() -> Self {
+        Self::from_u64(1)
+    }
+
+    fn add(&self, other: &Self, context: &str) -> Result<Self, Overflow> {
+        self.checked_add(other)
+            .ok_or_else(|| Overflow::new(context))
+    }
+
+    fn mul(&self, other: &Self, context: &str) -> Result<Self, Overflow> {
+        self.checked_mul(other)
+            .ok_or_else(|| Overflow::new(context))
+    }
+
+    fn pow2(exp: u32, context: &str) -> Result<Self, Overflow> {
+        Self::checked_pow2(exp).ok_or_else(|| Overflow::new(context))
+    }
+}
+
+macro_rules! impl_num {
+    ($($t:ty),*) => {
+        $(
+            impl Num for $t {
+                fn from_u64(n: u64) -> Self {
+                    n.try_into().unwrap()
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *other)
+                }
+
+                fn checked_pow2(exp: u32) -> Option<Self> {
+                    <$t>::checked_pow(2, exp)
+                }
+            }
+        )*
+    };
+}
+
+impl_num!(u64, u128);
+
+// Formats an answer for printing, or the error which stopped it being calculated.
+pub fn answer<N: fmt::Display, E: fmt::Display>(result: Result<N, E>) -> String {
+    match result {
+        Ok(n) => n.to_string(),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+pub fn checked_sum<N: Num>(
+    values: impl IntoIterator<Item = N>,
+    context: &str,
+) -> Result<N, Overflow> {
+    values
+        .into_iter()
+        .try_fold(N::zero(), |sum, n| sum.add(&n, context))
+}
+
+pub fn checked_product<N: Num>(
+    values: impl IntoIterator<Item = N>,
+    context: &str,
+) -> Result<N, Overflow> {
+    values
+        .into_iter()
+        .try_fold(N::one(), |product, n| product.mul(&n, context))
+}
+
+// Arbitrary precision unsigned integer. Only does what the solvers need: addition, subtraction,
+// multiplication, powers of two, comparison and printing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigUint {
+    // Base 2^32 digits, least significant first, with no trailing zeros. Zero is empty.
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    fn normalized(mut digits: Vec<u32>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Self { digits }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    // Divides in place by a single digit, returning the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+
+        self.digits.iter_mut().rev().for_each(|digit| {
+            let current = remainder << 32 | *digit as u64;
+            *digit = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        });
+
+        *self = Self::normalized(std::mem::take(&mut self.digits));
+
+        return remainder as u32;
+    }
+}
+
+impl Num for BigUint {
+    fn from_u64(n: u64) -> Self {
+        Self::normalized(vec![n as u32, (n >> 32) as u32])
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let len = self.digits.len().max(other.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+
+        (0..len).for_each(|i| {
+            let sum = *self.digits.get(i).unwrap_or(&0) as u64
+                + *other.digits.get(i).unwrap_or(&0) as u64
+                + carry;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        });
+        digits.push(carry as u32);
+
+        Some(Self::normalized(digits))
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if self < other {
+            return None;
+        }
+
+        let mut digits = Vec::with_capacity(self.digits.len());
+        let mut borrow = 0i64;
+
+        self.digits.iter().enumerate().for_each(|(i, &digit)| {
+            let mut difference = digit as i64 - *other.digits.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = 0;
+            if difference < 0 {
+                difference += 1 << 32;
+                borrow = 1;
+            }
+            digits.push(difference as u32);
+        });
+
+        Some(Self::normalized(digits))
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+
+        self.digits.iter().enumerate().for_each(|(i, &a)| {
+            let mut carry = 0u64;
+
+            other.digits.iter().enumerate().for_each(|(j, &b)| {
+                let current = digits[i + j] as u64 + a as u64 * b as u64 + carry;
+                digits[i + j] = current as u32;
+                carry = current >> 32;
+            });
+
+            let mut k = i + other.digits.len();
+            while carry > 0 {
+                let current = digits[k] as u64 + carry;
+                digits[k] = current as u32;
+                carry = current >> 32;
+                k += 1;
+            }
+        });
+
+        Some(Self::normalized(digits))
+    }
+
+    fn checked_pow2(exp: u32) -> Option<Self> {
+        let mut digits = vec![0; exp as usize / 32];
+        digits.push(1 << (exp % 32));
+
+        Some(Self::normalized(digits))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // Peel off nine decimal digits at a time
+        let mut n = self.clone();
+        let mut chunks = Vec::new();
+        while !n.is_zero() {
+            chunks.push(n.div_rem_small(1_000_000_000));
+        }
+
+        let mut out = chunks.pop().unwrap().to_string();
+        chunks
+            .iter()
+            .rev()
+            .for_each(|chunk| out.push_str(&format!("{:09}", chunk)));
+
+        f.pad(&out)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        Self::from_u64(n)
+    }
+}
+
+#[allow(dead_code)]
+fn big(n: u128) -> BigUint {
+    BigUint::from_u64((n >> 64) as u64)
+        .checked_mul(&BigUint::checked_pow2(64).unwrap())
+        .unwrap()
+        .checked_add(&BigUint::from_u64(n as u64))
+        .unwrap()
+}
+
+#[test]
+fn test_checked_u64() {
+    assert_eq!(5u64.add(&6, "test"), Ok(11));
+    assert_eq!(
+        u64::MAX.add(&1, "summing things"),
+        Err(Overflow::new("summing things"))
+    );
+    assert_eq!(u64::MAX.mul(&2, "test").unwrap_err().context, "test");
+    assert_eq!(u64::pow2(63, "test"), Ok(1 << 63));
+    assert!(u64::pow2(64, "test").is_err());
+
+    assert_eq!(checked_sum([1u64, 2, 3], "test"), Ok(6));
+    assert!(checked_sum([u64::MAX, 1], "test").is_err());
+    assert_eq!(checked_product([2u64, 3, 4], "test"), Ok(24));
+    assert_eq!(checked_product(Vec::<u64>::new(), "test"), Ok(1));
+    assert!(checked_product([u64::MAX, 2], "test").is_err());
+}
+
+#[test]
+fn test_answer() {
+    assert_eq!(answer(Ok::<u64, Overflow>(12)), "12");
+    assert_eq!(
+        answer(u64::MAX.add(&1, "part one")),
+        "error: arithmetic overflow in part one (rerun with --big for an exact answer)"
+    );
+}
+
+#[test]
+fn test_overflow_message() {
+    assert_eq!(
+        Overflow::new("the gear ratio sum").to_string(),
+        "arithmetic overflow in the gear ratio sum (rerun with --big for an exact answer)"
+    );
+}
+
+#[test]
+fn test_big_arithmetic() {
+    let a = u64::MAX as u128 * 3 + 17;
+    let b = u64::MAX as u128 + 5;
+
+    assert_eq!(big(a).checked_add(&big(b)), Some(big(a + b)));
+    assert_eq!(big(a).checked_sub(&big(b)), Some(big(a - b)));
+    assert_eq!(big(b).checked_sub(&big(a)), None);
+    assert_eq!(big(a).checked_sub(&big(a)), Some(BigUint::zero()));
+    assert_eq!(
+        big(u64::MAX as u128).checked_mul(&big(u64::MAX as u128)),
+        Some(big(u64::MAX as u128 * u64::MAX as u128))
+    );
+    assert_eq!(BigUint::pow2(100, "test"), Ok(big(1 << 100)));
+    assert_eq!(BigUint::zero().checked_mul(&big(a)), Some(BigUint::zero()));
+
+    assert!(big(a) > big(b));
+    assert!(big(1 << 64) > big(u64::MAX as u128));
+    assert_eq!(big(12).cmp(&big(12)), Ordering::Equal);
+}
+
+#[test]
+fn test_big_display() {
+    assert_eq!(BigUint::zero().to_string(), "0");
+    assert_eq!(big(42).to_string(), "42");
+    assert_eq!(big(1_000_000_000).to_string(), "1000000000");
+    assert_eq!(big(u128::MAX).to_string(), u128::MAX.to_string());
+    assert_eq!(
+        BigUint::pow2(128, "test").unwrap().to_string(),
+        "340282366920938463463374607431768211456"
+    );
+    assert_eq!(format!("{:>5}", big(42)), "   42");
+}
+
+#[test]
+fn test_big_never_overflows() {
+    let product = checked_product((1..=30).map(BigUint::from_u64), "test").unwrap();
+    assert_eq!(product.to_string(), "265252859812191058636308480000000");
+
+    let sum = checked_sum([u64::MAX; 4].map(BigUint::from), "test").unwrap();
+    assert_eq!(sum, big(u64::MAX as u128 * 4));
+}
@@ -0,0 +1,94 @@
+// Splitting work across threads. Results come back in the same order, and so are the same, as doing
+// the work on one thread, which can be forced with `set_single_threaded` for benchmarking.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SINGLE_THREADED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_single_threaded(single: bool) {
+    SINGLE_THREADED.store(single, Ordering::Relaxed);
+}
+
+// How many threads work is split across
+pub fn threads() -> usize {
+    match SINGLE_THREADED.load(Ordering::Relaxed) {
+        true => 1,
+        false => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    }
+}
+
+// `f` applied to every item, in order
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_with(threads(), items, f)
+}
+
+// The items are split into one run of neighbouring items per thread. A panic on any thread is
+// passed on as it was.
+pub fn map_with<T: Sync, R: Send>(
+    threads: usize,
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let threads = threads.min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles = items
+            .chunks(items.len().div_ceil(threads))
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn test_map_keeps_order() {
+    let items = (0..1000).collect::<Vec<u64>>();
+    let expected = items.iter().map(|n| n * n).collect::<Vec<_>>();
+
+    for threads in [1, 2, 3, 7, 64, 2000] {
+        assert_eq!(map_with(threads, &items, |n| n * n), expected);
+    }
+    assert_eq!(map(&items, |n| n * n), expected);
+    assert!(map_with(4, &[] as &[u64], |n| *n).is_empty());
+}
+
+#[test]
+fn test_map_uses_threads() {
+    let items = [(); 4];
+    let ids = map_with(4, &items, |_| std::thread::current().id());
+
+    assert_eq!(
+        ids.iter().collect::<std::collections::HashSet<_>>().len(),
+        4
+    );
+    assert!(map_with(1, &items, |_| std::thread::current().id())
+        .iter()
+        .all(|id| *id == std::thread::current().id()));
+}
+
+#[test]
+fn test_map_passes_on_panics() {
+    let panic = std::panic::catch_unwind(|| {
+        map_with(4, &[1, 2, 3, 4], |&n| {
+            if n == 3 {
+                panic!("three");
+            }
+            n
+        })
+    })
+    .unwrap_err();
+
+    assert_eq!(panic.downcast_ref::<&str>(), Some(&"three"));
+}
@@ -0,0 +1,109 @@
+// Reading puzzle input which may not be what it claims to be. Anything malformed is reported with
+// the line it's on instead of panicking, and solvers pass it on alongside overflows.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Overflow;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    // Counted from 1
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: &str) -> Self {
+        Self {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Why a solver couldn't produce an answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Parse(ParseError),
+    Overflow(Overflow),
+    // Input which couldn't be read at all, for solvers reading files themselves
+    Io(String),
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<Overflow> for Error {
+    fn from(e: Overflow) -> Self {
+        Self::Overflow(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "invalid input, {}", e),
+            Self::Overflow(e) => e.fmt(f),
+            Self::Io(e) => write!(f, "couldn't read input, {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Parses every non-blank line with `f`, numbering any error with the line it came from
+pub fn parse_lines<T>(
+    text: &str,
+    f: impl Fn(&str) -> Result<T, String>,
+) -> Result<Vec<T>, ParseError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| f(line).map_err(|e| ParseError::new(i + 1, &e)))
+        .collect()
+}
+
+pub fn number<T: FromStr>(s: &str) -> Result<T, String> {
+    s.parse()
+        .map_err(|_| format!("expected a number, found {:?}", s))
+}
+
+#[test]
+fn test_parse_lines() {
+    let text = "1\n\n  \n2\nthree\n";
+
+    assert_eq!(parse_lines("4\n5", number::<u8>), Ok(vec![4, 5]));
+    assert_eq!(parse_lines("", number::<u8>), Ok(vec![]));
+    assert_eq!(
+        parse_lines(text, number::<u8>),
+        Err(ParseError::new(5, "expected a number, found \"three\""))
+    );
+    assert_eq!(
+        parse_lines("300", number::<u8>).unwrap_err().to_string(),
+        "line 1: expected a number, found \"300\""
+    );
+}
+
+#[test]
+fn test_error_message() {
+    let parse: Error = ParseError::new(3, "missing bid").into();
+    let overflow: Error = Overflow::new("the total winnings").into();
+
+    assert_eq!(parse.to_string(), "invalid input, line 3: missing bid");
+    assert_eq!(
+        overflow.to_string(),
+        Overflow::new("the total winnings").to_string()
+    );
+}
@@ -0,0 +1,37 @@
+// Calibration documents: lines of lowercase letters with at least one digit somewhere in each.
+
+use common::gen::{Param, Rng, Sizes};
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "lines",
+        default: 1000,
+        help: "number of calibration lines",
+    },
+    Param {
+        name: "length",
+        default: 40,
+        help: "longest line, in characters",
+    },
+];
+
+pub fn generate(rng: &mut Rng, sizes: &Sizes) -> String {
+    let length = sizes.get("length").max(1);
+
+    (0..sizes.get("lines"))
+        .map(|_| {
+            let mut line = (0..rng.range(1, length))
+                .map(|_| match rng.chance(20) {
+                    true => char::from(b'0' + rng.range(0, 9) as u8),
+                    false => char::from(b'a' + rng.range(0, 25) as u8),
+                })
+                .collect::<Vec<_>>();
+
+            // Every line needs a calibration value
+            let at = rng.range(0, line.len() as u64 - 1) as usize;
+            line[at] = char::from(b'1' + rng.range(0, 8) as u8);
+
+            line.into_iter().collect::<String>() + "\n"
+        })
+        .collect()
+}
@@ -0,0 +1,92 @@
+#![allow(clippy::needless_return)]
+
+use common::bench::Bencher;
+use common::parse::parse_lines;
+use common::{checked_sum, Error, Num, Overflow, ParseError};
+
+pub mod gen;
+
+pub fn part_one<N: Num>(text: &str) -> Result<N, Error> {
+    return Ok(calibration_sum(&parse(text)?)?);
+}
+
+// Times parsing and solving separately, for `aoc bench`
+pub fn bench(text: &str, b: &mut Bencher) -> Result<(), Error> {
+    let digit_lines = b.run("parse", || parse(text))?;
+    b.run("part one", || calibration_sum::<u64>(&digit_lines))?;
+
+    return Ok(());
+}
+
+// The digits on each line, of which there has to be at least one
+fn parse(text: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    parse_lines(text, |line| {
+        let digits = line
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect::<Vec<_>>();
+
+        match digits.is_empty() {
+            true => Err(String::from("no digits")),
+            false => Ok(digits),
+        }
+    })
+}
+
+fn calibration_sum<N: Num>(digit_lines: &[Vec<char>]) -> Result<N, Overflow> {
+    let sum = checked_sum(
+        digit_lines.iter().map(|l| {
+            let first = l[0].to_digit(10).unwrap() as u64;
+            let last = l[l.len() - 1].to_digit(10).unwrap() as u64;
+
+            N::from_u64(first * 10 + last)
+        }),
+        "the calibration value sum",
+    );
+
+    return sum;
+}
+
+#[test]
+fn test_part_one() {
+    use common::{day_file, BigUint};
+
+    assert_eq!(part_one::<u64>(&day_file!("example")), Ok(142));
+    assert_eq!(part_one::<u64>(&day_file!("input")), Ok(54951));
+    assert_eq!(
+        part_one::<BigUint>(&day_file!("input")),
+        Ok(BigUint::from(54951))
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(part_one::<u64>(""), Ok(0));
+    assert_eq!(
+        part_one::<u64>(
+            "a1b
+
+none
+"
+        ),
+        Err(ParseError::new(3, "no digits").into())
+    );
+}
+
+#[test]
+fn test_generated_input() {
+    use common::gen::{Rng, Sizes};
+
+    let text = crate::gen::generate(&mut Rng::new(1), &Sizes::defaults(crate::gen::PARAMS));
+    let expected = text
+        .lines()
+        .map(|line| {
+            let first = line.chars().find(char::is_ascii_digit).unwrap();
+            let last = line.chars().rev().find(char::is_ascii_digit).unwrap();
+            format!("{}{}", first, last).parse::<u64>().unwrap()
+        })
+        .sum::<u64>();
+
+    assert_eq!(text.lines().count(), 1000);
+    assert_eq!(part_one::<u64>(&text), Ok(expected));
+}
@@ -1,27 +1,30 @@
+#![allow(clippy::needless_return)]
+
+use common::input::command_line_inputs;
+use common::{answer, day_file, BigUint, Num};
+use day01::part_one;
+
+// day01 [--big] [filename|-|--text input ...]
 fn main() {
-    println!["part one example: {}", part_one("example")];
-    println!["part one: {}", part_one("input")];
-}
+    let (big, inputs) = command_line_inputs();
 
-fn part_one(filename: &str) -> u32 {
-    let text = std::fs::read_to_string(filename)
-        .unwrap()
-        .trim()
-        .to_string();
-    let lines = text.lines();
-    let digit_lines = lines
-        .map(|line| line.chars().filter(|c| c.is_digit(10)).collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    match big {
+        true => run::<BigUint>(&inputs),
+        false => run::<u64>(&inputs),
+    }
+}
 
-    let items = digit_lines
-        .iter()
-        .map(|l| vec![*l.first().unwrap(), *l.last().unwrap()])
-        .collect::<Vec<_>>();
+fn run<N: Num>(inputs: &[(String, String)]) {
+    if !inputs.is_empty() {
+        inputs.iter().for_each(|(label, text)| {
+            println!("Part one {}: {}", label, answer(part_one::<N>(text)));
+        });
+        return;
+    }
 
-    let sum = items
-        .iter()
-        .map(|item| item.iter().collect::<String>().parse::<u32>().unwrap())
-        .sum::<u32>();
+    let example = day_file!("example");
+    let input = day_file!("input");
 
-    return sum;
+    println!["part one example: {}", answer(part_one::<N>(&example))];
+    println!["part one: {}", answer(part_one::<N>(&input))];
 }
@@ -0,0 +1,66 @@
+// Cube games: each round draws a handful of cubes of some of the colours in the bag.
+
+use common::gen::{Param, Rng, Sizes};
+
+const COLOR_NAMES: &[&str] = &[
+    "red", "green", "blue", "yellow", "purple", "orange", "white", "black", "pink", "brown",
+];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "games",
+        default: 100,
+        help: "number of games",
+    },
+    Param {
+        name: "rounds",
+        default: 6,
+        help: "most rounds in a game",
+    },
+    Param {
+        name: "colors",
+        default: 3,
+        help: "number of distinct colours",
+    },
+    Param {
+        name: "cubes",
+        default: 20,
+        help: "most cubes of one colour drawn in a round",
+    },
+];
+
+// The first colours are the puzzle's own, after that they're numbered.
+fn color_name(i: usize) -> String {
+    match COLOR_NAMES.get(i) {
+        Some(name) => name.to_string(),
+        None => format!("color{}", i),
+    }
+}
+
+pub fn generate(rng: &mut Rng, sizes: &Sizes) -> String {
+    let colors = (0..sizes.get("colors").max(1) as usize)
+        .map(color_name)
+        .collect::<Vec<_>>();
+    let max_rounds = sizes.get("rounds").max(1);
+    let max_cubes = sizes.get("cubes").max(1);
+
+    (1..=sizes.get("games"))
+        .map(|id| {
+            let rounds = (0..rng.range(1, max_rounds))
+                .map(|_| {
+                    let mut drawn = colors.iter().collect::<Vec<_>>();
+                    rng.shuffle(&mut drawn);
+                    drawn.truncate(rng.range(1, colors.len() as u64) as usize);
+
+                    drawn
+                        .iter()
+                        .map(|name| format!("{} {}", rng.range(1, max_cubes), name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect::<Vec<_>>();
+
+            format!("Game {}: {}\n", id, rounds.join("; "))
+        })
+        .collect()
+}
@@ -0,0 +1,268 @@
+// Estimates what is in the bag from every game in a file, assuming they were all played with the
+// same bag.
+//
+// The minimal bag is the largest draw of each color in any round, since every cube in a round is
+// in the bag at the same time. On top of that each cube drawn is treated as an independent draw
+// with replacement, which makes the maximum likelihood estimate of a color's share of the bag its
+// share of all cubes seen. The bag is then scaled up to the smallest total that fits the minimal
+// bag at those shares. Intervals are Wilson score intervals on the shares, scaled by the same
+// total, and don't account for uncertainty in the total itself.
+
+use std::collections::BTreeMap;
+
+use common::json;
+
+use crate::{ColorSet, Game};
+
+#[derive(Debug)]
+pub struct Estimate {
+    pub color: String,
+    pub minimal: u32,
+    pub seen: u64,
+    pub share: f64,
+    pub share_interval: (f64, f64),
+    pub count: f64,
+    pub count_interval: (f64, f64),
+}
+
+#[derive(Debug)]
+pub struct Inference {
+    pub confidence: f64,
+    pub minimal: ColorSet,
+    pub total: f64,
+    pub estimates: Vec<Estimate>,
+}
+
+impl Inference {
+    pub fn new(games: &[Game], confidence: f64) -> Self {
+        let mut minimal = ColorSet::new();
+        let mut seen: BTreeMap<&str, u64> = BTreeMap::new();
+
+        games.iter().for_each(|game| {
+            minimal.update_if_greater_all(&game.max_values());
+
+            game.rounds.iter().flatten().for_each(|color| {
+                *seen.entry(color.name.as_str()).or_insert(0) += color.n as u64;
+            });
+        });
+
+        let drawn = seen.values().sum::<u64>();
+        let seen = |name: &str| seen.get(name).copied().unwrap_or(0);
+        let share = |name: &str| seen(name) as f64 / drawn as f64;
+
+        let total = minimal
+            .counts
+            .iter()
+            .filter(|(name, _)| share(name) > 0.0)
+            .map(|(name, &n)| n as f64 / share(name))
+            .fold(0.0, f64::max);
+
+        let z = z_score(confidence);
+
+        let estimates = minimal
+            .counts
+            .iter()
+            .map(|(name, &n)| {
+                let (low, high) = wilson_interval(share(name), drawn as f64, z);
+
+                Estimate {
+                    color: name.clone(),
+                    minimal: n,
+                    seen: seen(name),
+                    share: share(name),
+                    share_interval: (low, high),
+                    count: f64::max(n as f64, total * share(name)),
+                    count_interval: (
+                        f64::max(n as f64, total * low),
+                        f64::max(n as f64, total * high),
+                    ),
+                }
+            })
+            .collect();
+
+        return Self {
+            confidence,
+            minimal,
+            total,
+            estimates,
+        };
+    }
+
+    pub fn table(&self) -> String {
+        let level = format!("{}%", self.confidence * 100.0);
+        let mut out = format!("Minimal bag: {}\n", self.minimal);
+
+        out.push_str(&format!(
+            "{:<12} {:>8} {:>8} {:>7} {:>17} {:>9} {:>19}\n",
+            "Color",
+            "Minimal",
+            "Seen",
+            "Share",
+            format!("{} share", level),
+            "Estimate",
+            format!("{} estimate", level)
+        ));
+
+        self.estimates.iter().for_each(|e| {
+            out.push_str(&format!(
+                "{:<12} {:>8} {:>8} {:>7.3} {:>17} {:>9.1} {:>19}\n",
+                e.color,
+                e.minimal,
+                e.seen,
+                e.share,
+                format!("{:.3} - {:.3}", e.share_interval.0, e.share_interval.1),
+                e.count,
+                format!("{:.1} - {:.1}", e.count_interval.0, e.count_interval.1)
+            ))
+        });
+
+        out.push_str(&format!("Estimated total: {:.1}\n", self.total));
+
+        return out;
+    }
+
+    pub fn json(&self) -> String {
+        let estimates = self
+            .estimates
+            .iter()
+            .map(|e| {
+                format!(
+                    "{{\"color\":{},\"minimal\":{},\"seen\":{},\"share\":{},\"share_interval\":[{},{}],\"count\":{},\"count_interval\":[{},{}]}}",
+                    json::string(&e.color),
+                    e.minimal,
+                    e.seen,
+                    json_number(e.share),
+                    json_number(e.share_interval.0),
+                    json_number(e.share_interval.1),
+                    json_number(e.count),
+                    json_number(e.count_interval.0),
+                    json_number(e.count_interval.1)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        return format!(
+            "{{\"confidence\":{},\"total\":{},\"estimates\":[{}]}}",
+            self.confidence,
+            json_number(self.total),
+            estimates
+        );
+    }
+}
+
+// JSON has no NaN, which is the share of every color when no cubes were drawn at all
+fn json_number(n: f64) -> String {
+    match n.is_finite() {
+        true => n.to_string(),
+        false => String::from("null"),
+    }
+}
+
+fn wilson_interval(p: f64, n: f64, z: f64) -> (f64, f64) {
+    if n == 0.0 {
+        return (0.0, 1.0);
+    }
+
+    let z2 = z * z;
+    let denominator = 1.0 + z2 / n;
+    let centre = (p + z2 / (2.0 * n)) / denominator;
+    let half = z / denominator * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+
+    (f64::max(0.0, centre - half), f64::min(1.0, centre + half))
+}
+
+// Two sided z score for a confidence level, using Acklam's approximation of the inverse normal
+// distribution. Good to about 1e-9, which is plenty here.
+pub fn z_score(confidence: f64) -> f64 {
+    assert!(
+        confidence > 0.0 && confidence < 1.0,
+        "confidence must be between 0 and 1"
+    );
+
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+
+    // Upper quantile, so p is always above one half
+    let p = 1.0 - (1.0 - confidence) / 2.0;
+
+    if p <= 0.97575 {
+        let q = p - 0.5;
+        let r = q * q;
+
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    }
+}
+
+#[test]
+fn test_z_score() {
+    assert!((z_score(0.95) - 1.959964).abs() < 1e-6);
+    assert!((z_score(0.90) - 1.644854).abs() < 1e-6);
+    assert!((z_score(0.99) - 2.575829).abs() < 1e-6);
+    assert!((z_score(0.999) - 3.290527).abs() < 1e-6);
+}
+
+#[test]
+fn test_inference_without_cubes() {
+    let games = crate::parse("Game 1: 0 red").unwrap();
+    let json = Inference::new(&games, 0.95).json();
+
+    assert!(json.contains("\"share\":null"));
+    assert!(!json.contains("NaN"));
+}
+
+#[test]
+fn test_inference() {
+    let games = crate::parse(&common::day_file!("example")).unwrap();
+    let inference = Inference::new(&games, 0.95);
+
+    assert_eq!(inference.minimal.to_string(), "15 blue, 13 green, 20 red");
+
+    let shares = inference.estimates.iter().map(|e| e.share).sum::<f64>();
+    assert!((shares - 1.0).abs() < 1e-9);
+
+    // Red is the color holding the total up
+    let red = &inference.estimates[2];
+    assert_eq!(red.seen, 61);
+    assert!((inference.total - 20.0 * 159.0 / 61.0).abs() < 1e-9);
+    assert!((red.count - 20.0).abs() < 1e-9);
+
+    inference.estimates.iter().for_each(|e| {
+        assert!(e.count >= e.minimal as f64);
+        assert!(e.share_interval.0 < e.share && e.share < e.share_interval.1);
+        assert!(e.count_interval.0 <= e.count && e.count <= e.count_interval.1);
+    });
+}
@@ -0,0 +1,610 @@
+#![allow(clippy::needless_return)]
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use common::bench::Bencher;
+use common::day_file;
+use common::input::read_all_or_exit;
+use common::json;
+use common::parse::{number, parse_lines};
+use common::{checked_product, checked_sum, Error, Num, Overflow, ParseError};
+
+pub mod gen;
+mod infer;
+mod optimise;
+mod report;
+
+// Defined by problem
+const PUZZLE_BAG: &str = "12 red, 13 green, 14 blue";
+
+struct Game {
+    id: u32,
+    rounds: Vec<Vec<Color>>,
+}
+
+impl Game {
+    fn parse(line: &str) -> Result<Self, String> {
+        let mut iter = line.split(": ");
+        let (id, rounds) = match (iter.next(), iter.next(), iter.next()) {
+            (Some(id), Some(rounds), None) => (id, rounds),
+            _ => return Err(String::from("expected \"Game <id>: <rounds>\"")),
+        };
+
+        let id = number(id.rsplit(' ').next().unwrap_or_default())?;
+
+        let rounds = rounds
+            .split("; ")
+            .map(|round| round.split(", ").map(Color::parse).collect())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Game { id, rounds })
+    }
+
+    fn check(&self, bag: &ColorSet) -> bool {
+        self.rounds
+            .iter()
+            .all(|round| round.iter().all(|color| color.check(bag)))
+    }
+
+    fn colors(&self) -> impl Iterator<Item = &str> {
+        self.rounds
+            .iter()
+            .flat_map(|round| round.iter().map(|color| color.name.as_str()))
+    }
+
+    fn max_values(&self) -> ColorSet {
+        let mut max_values = ColorSet::new();
+
+        self.rounds.iter().for_each(|round| {
+            round.iter().for_each(|color| {
+                max_values.update_if_greater(color);
+            })
+        });
+
+        return max_values;
+    }
+}
+
+// Highest count seen for each color, keyed by color name.
+#[derive(Debug, Default, PartialEq)]
+struct ColorSet {
+    counts: BTreeMap<String, u32>,
+}
+
+impl ColorSet {
+    fn new() -> Self {
+        ColorSet::default()
+    }
+
+    // Bags are written the same way as a round, e.g. "12 red, 13 green, 14 blue"
+    fn parse(s: &str) -> Result<Self, String> {
+        s.trim()
+            .split(", ")
+            .map(Color::parse)
+            .try_fold(ColorSet::new(), |mut set, color| {
+                set.update_if_greater(&color?);
+                Ok(set)
+            })
+    }
+
+    fn puzzle_bag() -> Self {
+        Self::parse(PUZZLE_BAG).unwrap()
+    }
+
+    fn update_if_greater_all(&mut self, other: &ColorSet) {
+        other.counts.iter().for_each(|(name, &n)| {
+            self.update_if_greater(&Color {
+                name: name.clone(),
+                n,
+            })
+        });
+    }
+
+    fn get(&self, name: &str) -> u32 {
+        self.counts.get(name).copied().unwrap_or(0)
+    }
+
+    fn update_if_greater(&mut self, color: &Color) {
+        let n = self.counts.entry(color.name.clone()).or_insert(0);
+        if color.n > *n {
+            *n = color.n
+        }
+    }
+
+    // Product over every color in the palette, so a color never drawn in this game (but seen in
+    // others) makes the power zero.
+    fn powers<N: Num>(&self, palette: &BTreeSet<String>) -> Result<N, Overflow> {
+        checked_product(
+            palette
+                .iter()
+                .map(|name| N::from_u64(self.get(name) as u64)),
+            "a game's power",
+        )
+    }
+}
+
+// A number of cubes of one color. Colors are whatever names turn up in the input.
+#[derive(Debug, PartialEq)]
+struct Color {
+    name: String,
+    n: u32,
+}
+
+impl Color {
+    fn parse(line: &str) -> Result<Self, String> {
+        let mut iter = line.split(' ');
+
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(n), Some(name), None) if !name.is_empty() => Ok(Color {
+                name: name.to_string(),
+                n: number(n)?,
+            }),
+            _ => Err(format!("expected a count and a color, found {:?}", line)),
+        }
+    }
+
+    // Any color the bag doesn't list has no cubes in it.
+    fn check(&self, bag: &ColorSet) -> bool {
+        self.n <= bag.get(&self.name)
+    }
+}
+
+impl std::fmt::Display for ColorSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let colors = self
+            .counts
+            .iter()
+            .map(|(name, n)| format!("{} {}", n, name))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", colors.join(", "))
+    }
+}
+
+fn parse(text: &str) -> Result<Vec<Game>, ParseError> {
+    parse_lines(text, Game::parse)
+}
+
+fn possible_ids(games: &[Game], bag: &ColorSet) -> Vec<u32> {
+    games
+        .iter()
+        .filter(|game| game.check(bag))
+        .map(|game| game.id)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Output {
+    // The possible game ids for each bag
+    Ids,
+    // Why each impossible game is impossible
+    Table,
+    Json,
+}
+
+// The options `check_bags` takes, with the puzzle bag if no others were given
+struct BagOptions {
+    bags: Vec<ColorSet>,
+    inputs: Vec<String>,
+    output: Output,
+    confidence: Option<f64>,
+    budget: Option<u32>,
+    objective: optimise::Objective,
+}
+
+impl BagOptions {
+    fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            bags: Vec::new(),
+            inputs: Vec::new(),
+            output: Output::Ids,
+            confidence: None,
+            budget: None,
+            objective: optimise::Objective::Count,
+        };
+        let mut args = args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {}", arg))
+            };
+
+            match arg.as_str() {
+                "--infer" => {
+                    options.confidence = match args.peek().and_then(|c| c.parse::<f64>().ok()) {
+                        Some(c) if c > 0.0 && c < 1.0 => {
+                            args.next();
+                            Some(c)
+                        }
+                        Some(c) => {
+                            return Err(format!(
+                                "--infer confidence must be between 0 and 1, got {}",
+                                c
+                            ))
+                        }
+                        None => Some(0.95),
+                    }
+                }
+                "--optimise" => {
+                    let value = value()?;
+                    options.budget = Some(value.parse().map_err(|_| {
+                        format!("--optimise needs a number of cubes, got {}", value)
+                    })?)
+                }
+                "--by-ids" => options.objective = optimise::Objective::IdSum,
+                "--report" => options.output = Output::Table,
+                "--json" => options.output = Output::Json,
+                "--bag" => options.bags.push(parse_bag(value()?)?),
+                "--bags" => {
+                    let filename = value()?;
+                    let text = std::fs::read_to_string(filename)
+                        .map_err(|e| format!("{}: {}", filename, e))?;
+
+                    options.bags.extend(
+                        parse_lines(&text, ColorSet::parse)
+                            .map_err(|e| format!("{}: invalid bag on {}", filename, e))?,
+                    );
+                }
+                "--text" => {
+                    let text = value()?;
+                    options.inputs.extend([arg.clone(), text.clone()]);
+                }
+                other if other.starts_with("--") => {
+                    return Err(format!("unknown option {}", other))
+                }
+                _ => options.inputs.push(arg.clone()),
+            }
+        }
+
+        if options.bags.is_empty() {
+            options.bags.push(ColorSet::puzzle_bag());
+        }
+
+        return Ok(options);
+    }
+}
+
+// Checks every game against every bag given, printing the possible game ids per bag.
+//
+//   --bag "12 red, 13 green, 14 blue"  may be repeated
+//   --bags <filename>                  one bag per line
+//   --report                           explain impossible games instead of listing ids
+//   --json                             the same, as one line of JSON per file and bag
+//   --infer [confidence]               estimate the bag from the games instead, at a confidence
+//                                      level between 0 and 1 (default 0.95)
+//   --optimise <cubes>                 find the bag of that many cubes admitting the most games
+//   --by-ids                           ...or the largest sum of game ids
+//
+// Anything else is a game file, `-` for stdin, or `--text <games>`. Without any bags the puzzle's
+// bag is used, and without any games the day's example and input are checked.
+pub fn check_bags(args: &[String]) {
+    let BagOptions {
+        bags,
+        inputs,
+        output,
+        confidence,
+        budget,
+        objective,
+    } = BagOptions::from_args(args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    let inputs = match inputs.is_empty() {
+        true => vec![
+            (String::from("example"), day_file!("example")),
+            (String::from("input"), day_file!("input")),
+        ],
+        false => read_all_or_exit(&inputs),
+    };
+
+    inputs.iter().for_each(|(label, text)| {
+        let games = parse(text).unwrap_or_else(|e| {
+            eprintln!("{}: {}", label, e);
+            std::process::exit(1);
+        });
+
+        if let Some(budget) = budget {
+            let solution = optimise::optimise(&games, budget, objective);
+
+            return println!(
+                "{}: best bag of {} cubes is {} with {} spare, admitting {} games with id sum {}: {}",
+                label,
+                budget,
+                solution.bag,
+                solution.spare,
+                solution.games.len(),
+                solution.games.iter().map(|&id| id as u64).sum::<u64>(),
+                solution
+                    .games
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        }
+
+        if let Some(confidence) = confidence {
+            let inference = infer::Inference::new(&games, confidence);
+
+            return match output {
+                Output::Json => println!(
+                    "{{\"file\":{},\"inference\":{}}}",
+                    json::string(label),
+                    inference.json()
+                ),
+                Output::Ids | Output::Table => print!("{}:\n{}", label, inference.table()),
+            };
+        }
+
+        if output == Output::Json {
+            return bags.iter().for_each(|bag| {
+                println!(
+                    "{{\"file\":{},\"report\":{}}}",
+                    json::string(label),
+                    report::Report::new(&games, bag).json()
+                )
+            });
+        }
+
+        println!("{}:", label);
+
+        bags.iter().for_each(|bag| {
+            if output == Output::Table {
+                return print!("{}", report::Report::new(&games, bag).table());
+            }
+
+            let ids = possible_ids(&games, bag);
+
+            println!(
+                "  {}: {} possible, id sum {}: {}",
+                bag,
+                ids.len(),
+                ids.iter().map(|&id| id as u64).sum::<u64>(),
+                ids.iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        });
+    });
+}
+
+fn parse_bag(s: &str) -> Result<ColorSet, String> {
+    ColorSet::parse(s).map_err(|e| format!("invalid bag {:?}: {}", s, e))
+}
+
+pub fn part_one<N: Num>(text: &str) -> Result<N, Error> {
+    return Ok(possible_id_sum(&parse(text)?)?);
+}
+
+pub fn part_two<N: Num>(text: &str) -> Result<N, Error> {
+    return Ok(power_sum(&parse(text)?)?);
+}
+
+// Times parsing and solving separately, for `aoc bench`
+pub fn bench(text: &str, b: &mut Bencher) -> Result<(), Error> {
+    let games = b.run("parse", || parse(text))?;
+    b.run("part one", || possible_id_sum::<u64>(&games))?;
+    b.run("part two", || power_sum::<u64>(&games))?;
+
+    return Ok(());
+}
+
+fn possible_id_sum<N: Num>(games: &[Game]) -> Result<N, Overflow> {
+    let sum = checked_sum(
+        possible_ids(games, &ColorSet::puzzle_bag())
+            .iter()
+            .map(|&id| N::from_u64(id as u64)),
+        "the game id sum",
+    );
+
+    return sum;
+}
+
+fn power_sum<N: Num>(games: &[Game]) -> Result<N, Overflow> {
+    let palette = games
+        .iter()
+        .flat_map(|game| game.colors().map(String::from))
+        .collect::<BTreeSet<_>>();
+
+    let max_values = games
+        .iter()
+        .map(|game| game.max_values())
+        .collect::<Vec<_>>();
+
+    let powers = max_values
+        .iter()
+        .map(|values| values.powers::<N>(&palette))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    return checked_sum(powers, "the power sum");
+}
+
+#[test]
+fn test_part_one() {
+    use common::{day_file, BigUint};
+
+    assert_eq!(part_one::<u64>(&day_file!("example")), Ok(8));
+    assert_eq!(part_one::<u64>(&day_file!("input")), Ok(2727));
+    assert_eq!(
+        part_one::<BigUint>(&day_file!("input")),
+        Ok(BigUint::from(2727))
+    );
+}
+
+#[test]
+fn test_part_two() {
+    use common::{day_file, BigUint};
+
+    assert_eq!(part_two::<u64>(&day_file!("example")), Ok(2286));
+    assert_eq!(part_two::<u64>(&day_file!("input")), Ok(56580));
+    assert_eq!(
+        part_two::<BigUint>(&day_file!("input")),
+        Ok(BigUint::from(56580))
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    let error = |line, message: &str| Err(ParseError::new(line, message).into());
+
+    assert_eq!(part_one::<u64>(""), Ok(0));
+    assert_eq!(
+        part_one::<u64>("Game 1: 3 blue\nGame two: 3 blue"),
+        error(2, "expected a number, found \"two\"")
+    );
+    assert_eq!(
+        part_one::<u64>("Game 1 3 blue"),
+        error(1, "expected \"Game <id>: <rounds>\"")
+    );
+    assert_eq!(
+        part_two::<u64>("Game 1: 3 blue; "),
+        error(1, "expected a count and a color, found \"\"")
+    );
+    assert_eq!(
+        part_two::<u64>("Game 1: 3 blue, 4 dark red"),
+        error(1, "expected a count and a color, found \"4 dark red\"")
+    );
+    assert!(ColorSet::parse("12 red, lots green").is_err());
+}
+
+#[test]
+fn test_dynamic_colors() {
+    let game = Game::parse("Game 7: 3 teal, 2 red; 5 teal, 1 mauve").unwrap();
+
+    assert_eq!(
+        game.rounds[1][1],
+        Color {
+            name: String::from("mauve"),
+            n: 1
+        }
+    );
+    assert!(!game.check(&ColorSet::parse(PUZZLE_BAG).unwrap()));
+    assert!(game.check(&ColorSet::parse("5 teal, 2 red, 1 mauve").unwrap()));
+
+    let max_values = game.max_values();
+    assert_eq!(max_values.get("teal"), 5);
+    assert_eq!(max_values.get("red"), 2);
+    assert_eq!(max_values.get("blue"), 0);
+
+    let palette = game.colors().map(String::from).collect::<BTreeSet<_>>();
+    assert_eq!(max_values.powers::<u64>(&palette), Ok(10));
+
+    let palette = ["teal", "red", "mauve", "blue"].map(String::from).into();
+    assert_eq!(max_values.powers::<u64>(&palette), Ok(0));
+}
+
+#[test]
+fn test_bags() {
+    let bag = ColorSet::parse(PUZZLE_BAG).unwrap();
+    assert_eq!(bag.get("red"), 12);
+    assert_eq!(bag.get("blue"), 14);
+    assert_eq!(bag.get("teal"), 0);
+    assert_eq!(bag.to_string(), "14 blue, 13 green, 12 red");
+
+    let games = parse(&common::day_file!("example")).unwrap();
+    assert_eq!(possible_ids(&games, &bag), vec![1, 2, 5]);
+    assert_eq!(
+        possible_ids(
+            &games,
+            &ColorSet::parse("20 red, 13 green, 15 blue").unwrap()
+        ),
+        vec![1, 2, 3, 4, 5]
+    );
+    assert_eq!(
+        possible_ids(&games, &ColorSet::parse("4 red, 3 green").unwrap()),
+        Vec::<u32>::new()
+    );
+}
+
+#[test]
+fn test_bag_options() {
+    let options = |s: &str| {
+        BagOptions::from_args(&s.split_whitespace().map(String::from).collect::<Vec<_>>())
+    };
+
+    let parsed = options("--infer 0.9 --optimise 40 example").unwrap();
+    assert_eq!(parsed.confidence, Some(0.9));
+    assert_eq!(parsed.budget, Some(40));
+    assert_eq!(parsed.inputs, vec!["example"]);
+    assert_eq!(parsed.bags.len(), 1);
+
+    assert_eq!(
+        options("--bag").err(),
+        Some(String::from("missing value for --bag"))
+    );
+    assert_eq!(
+        options("--optimise lots").err(),
+        Some(String::from("--optimise needs a number of cubes, got lots"))
+    );
+    assert!(options("--bag 3_red").is_err());
+    assert!(options("--bags /nonexistent").is_err());
+    assert!(options("--infer 95").is_err());
+    assert_eq!(
+        options("--bgs bags").err(),
+        Some(String::from("unknown option --bgs"))
+    );
+    assert_eq!(
+        options("--report --text games").unwrap().inputs,
+        vec!["--text", "games"]
+    );
+}
+
+#[test]
+fn test_powers_overflow() {
+    use common::BigUint;
+
+    let round = (0..4)
+        .map(|i| format!("4000000000 c{}", i))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let max_values = Game::parse(&format!("Game 1: {}", round))
+        .unwrap()
+        .max_values();
+    let palette = max_values.counts.keys().cloned().collect::<BTreeSet<_>>();
+
+    assert!(max_values.powers::<u64>(&palette).is_err());
+    assert_eq!(
+        max_values.powers::<BigUint>(&palette).unwrap().to_string(),
+        "256000000000000000000000000000000000000"
+    );
+}
+
+#[allow(dead_code)]
+fn format_game(game: &Game) -> String {
+    let rounds = game
+        .rounds
+        .iter()
+        .map(|round| {
+            round
+                .iter()
+                .map(|c| format!("{} {}", c.n, c.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .collect::<Vec<_>>();
+
+    format!("Game {}: {}", game.id, rounds.join("; "))
+}
+
+#[test]
+fn test_generated_input() {
+    use crate::gen::{generate, PARAMS};
+    use common::gen::{Rng, Sizes};
+
+    for (seed, colors) in [(1, 3), (2, 1), (3, 12)] {
+        let sizes = Sizes::defaults(PARAMS).with("colors", colors);
+        let text = generate(&mut Rng::new(seed), &sizes);
+        let games = parse(&text).unwrap();
+
+        assert_eq!(games.len(), 100);
+        text.lines()
+            .zip(games.iter())
+            .for_each(|(line, game)| assert_eq!(format_game(game), line));
+
+        let palette = games.iter().flat_map(Game::colors).collect::<BTreeSet<_>>();
+        assert_eq!(palette.len(), colors as usize);
+    }
+}
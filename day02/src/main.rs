@@ -1,154 +1,45 @@
-// Defined by problem
-fn main() {
-    println!("Part one example: {}", part_one("example"));
-    println!("Part one input: {}", part_one("input"));
-
-    println!("Part two example: {}", part_two("example"));
-    println!("Part two example: {}", part_two("input"));
-}
-
-struct Game {
-    id: u32,
-    rounds: Vec<Vec<Color>>,
-}
+#![allow(clippy::needless_return)]
 
-impl Game {
-    fn parse(line: &str) -> Self {
-        let mut iter = line.split(": ");
-        let (id, rounds) = (iter.next().unwrap(), iter.next().unwrap());
-        if iter.next().is_some() {
-            panic!("Invalid game");
-        }
+use common::input::{command_line, read_all_or_exit};
+use common::{answer, day_file, BigUint, Num};
+use day02::{check_bags, part_one, part_two};
 
-        let id = id.split(" ").last().unwrap().parse::<u32>().unwrap();
-
-        let rounds = rounds
-            .split("; ")
-            .map(|round| round.split(", ").map(Color::parse).collect())
-            .collect();
-
-        Game { id, rounds }
-    }
-
-    fn check(&self) -> bool {
-        self.rounds
-            .iter()
-            .all(|round| round.iter().all(Color::check))
-    }
-
-    fn max_values(&self) -> ColorSet {
-        let mut max_values = ColorSet::new();
-
-        self.rounds.iter().for_each(|round| {
-            round.iter().for_each(|color| {
-                max_values.update_if_greater(color);
-            })
-        });
-
-        return max_values;
-    }
-}
-
-#[derive(Debug)]
-struct ColorSet {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
+// day02 [--big] [filename|-|--text input ...]
+//
+// Any other option, such as --bag, checks games against bags instead. See `check_bags`.
+fn main() {
+    let (big, args) = command_line();
 
-impl ColorSet {
-    fn new() -> Self {
-        ColorSet {
-            red: 0,
-            green: 0,
-            blue: 0,
-        }
+    if args
+        .iter()
+        .any(|arg| arg.starts_with("--") && arg != "--text")
+    {
+        return check_bags(&args);
     }
 
-    fn update_if_greater(&mut self, color: &Color) {
-        match color {
-            Color::Red(n) => {
-                if *n > self.red {
-                    self.red = *n
-                }
-            }
-            Color::Green(n) => {
-                if *n > self.green {
-                    self.green = *n
-                }
-            }
-            Color::Blue(n) => {
-                if *n > self.blue {
-                    self.blue = *n
-                }
-            }
-        }
-    }
+    let inputs = read_all_or_exit(&args);
 
-    fn powers(&self) -> u32 {
-        self.red * self.green * self.blue
+    match big {
+        true => run::<BigUint>(&inputs),
+        false => run::<u64>(&inputs),
     }
 }
 
-enum Color {
-    Red(u32),
-    Green(u32),
-    Blue(u32),
-}
-
-impl Color {
-    const RED_LIMIT: u32 = 12;
-    const GREEN_LIMI: u32 = 13;
-    const BLUE_LIMI: u32 = 14;
-
-    fn parse(line: &str) -> Self {
-        let mut iter = line.split(" ");
-
-        let (n, color) = (iter.next().unwrap(), iter.next().unwrap());
-        if iter.next().is_some() {
-            panic!("Invalid color");
-        }
-
-        match (n.parse::<u32>().unwrap(), color) {
-            (n, "red") => Color::Red(n),
-            (n, "green") => Color::Green(n),
-            (n, "blue") => Color::Blue(n),
-            _ => panic!("Invalid color"),
-        }
-    }
-
-    fn check(&self) -> bool {
-        match self {
-            Color::Red(n) => n <= &Color::RED_LIMIT,
-            Color::Green(n) => n <= &Color::GREEN_LIMI,
-            Color::Blue(n) => n <= &Color::BLUE_LIMI,
-        }
+fn run<N: Num>(inputs: &[(String, String)]) {
+    if !inputs.is_empty() {
+        inputs.iter().for_each(|(label, text)| {
+            println!("Part one {}: {}", label, answer(part_one::<N>(text)));
+            println!("Part two {}: {}", label, answer(part_two::<N>(text)));
+        });
+        return;
     }
-}
-
-fn part_one(filename: &str) -> u32 {
-    let text = std::fs::read_to_string(filename).unwrap();
-    let games = text.trim().lines().map(Game::parse).collect::<Vec<_>>();
-
-    let sum = games
-        .iter()
-        .filter(|game| game.check())
-        .map(|game| game.id)
-        .sum::<u32>();
 
-    return sum;
-}
-
-fn part_two(filename: &str) -> u32 {
-    let text = std::fs::read_to_string(filename).unwrap();
-    let games = text.trim().lines().map(Game::parse).collect::<Vec<_>>();
-
-    let max_values = games
-        .iter()
-        .map(|game| game.max_values())
-        .collect::<Vec<_>>();
+    let example = day_file!("example");
+    let input = day_file!("input");
 
-    let power_sum = max_values.iter().map(|values| values.powers()).sum::<u32>();
+    println!("Part one example: {}", answer(part_one::<N>(&example)));
+    println!("Part one input: {}", answer(part_one::<N>(&input)));
 
-    return power_sum;
+    println!("Part two example: {}", answer(part_two::<N>(&example)));
+    println!("Part two input: {}", answer(part_two::<N>(&input)));
 }
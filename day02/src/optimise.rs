@@ -0,0 +1,247 @@
+// Finds the bag which makes the most games possible for a fixed number of cubes.
+//
+// A game is possible exactly when the bag holds at least its `max_values` of every color, so each
+// game is a requirement vector and the question is which requirements to cover within the budget.
+// Some optimal bag only ever holds, for each color, exactly the requirement of one of the games it
+// admits (any cubes left over can go anywhere), so the search only tries those values. Colors are
+// fixed one at a time, dropping games the bag can no longer admit, and branches which couldn't
+// beat the best bag so far even by admitting every game left are skipped.
+
+use crate::{Color, ColorSet, Game};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+    // As many games as possible
+    Count,
+    // The largest sum of game ids
+    IdSum,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Solution {
+    pub bag: ColorSet,
+    // Cubes left over after meeting every requirement, which can go in as any color
+    pub spare: u32,
+    pub games: Vec<u32>,
+    pub score: u64,
+}
+
+struct Search<'a> {
+    colors: Vec<String>,
+    requirements: Vec<Vec<u32>>,
+    weights: Vec<u64>,
+    games: &'a [Game],
+    best: Option<(u64, Vec<u32>)>,
+}
+
+pub fn optimise(games: &[Game], budget: u32, objective: Objective) -> Solution {
+    let max_values = games.iter().map(Game::max_values).collect::<Vec<_>>();
+
+    let mut colors = max_values
+        .iter()
+        .flat_map(|values| values.counts.keys().cloned())
+        .collect::<Vec<_>>();
+    colors.sort();
+    colors.dedup();
+
+    let requirements = max_values
+        .iter()
+        .map(|values| colors.iter().map(|name| values.get(name)).collect())
+        .collect();
+
+    let weights = games
+        .iter()
+        .map(|game| match objective {
+            Objective::Count => 1,
+            Objective::IdSum => game.id as u64,
+        })
+        .collect();
+
+    let mut search = Search {
+        colors,
+        requirements,
+        weights,
+        games,
+        best: None,
+    };
+
+    let alive = (0..games.len()).collect::<Vec<_>>();
+    search.search(&alive, budget, &mut Vec::new());
+
+    return search.solution(budget);
+}
+
+impl<'a> Search<'a> {
+    fn score(&self, alive: &[usize]) -> u64 {
+        alive.iter().map(|&g| self.weights[g]).sum()
+    }
+
+    fn search(&mut self, alive: &[usize], budget: u32, thresholds: &mut Vec<u32>) {
+        let bound = self.score(alive);
+        if let Some((best, _)) = &self.best {
+            if bound <= *best {
+                return;
+            }
+        }
+
+        let color = thresholds.len();
+        if color == self.colors.len() {
+            self.best = Some((bound, thresholds.clone()));
+            return;
+        }
+
+        let mut candidates = alive
+            .iter()
+            .map(|&g| self.requirements[g][color])
+            .filter(|&n| n <= budget)
+            .chain([0])
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        // Generous thresholds first, so a good bag turns up early and prunes the rest
+        for &threshold in candidates.iter().rev() {
+            let next = alive
+                .iter()
+                .copied()
+                .filter(|&g| self.requirements[g][color] <= threshold)
+                .collect::<Vec<_>>();
+
+            thresholds.push(threshold);
+            self.search(&next, budget - threshold, thresholds);
+            thresholds.pop();
+        }
+    }
+
+    fn solution(&self, budget: u32) -> Solution {
+        let (score, thresholds) = self
+            .best
+            .clone()
+            .unwrap_or_else(|| (0, vec![0; self.colors.len()]));
+
+        let mut bag = ColorSet::new();
+        self.colors
+            .iter()
+            .zip(thresholds.iter())
+            .for_each(|(name, &n)| {
+                bag.update_if_greater(&Color {
+                    name: name.clone(),
+                    n,
+                })
+            });
+
+        let games = self
+            .games
+            .iter()
+            .filter(|game| game.check(&bag))
+            .map(|game| game.id)
+            .collect();
+
+        return Solution {
+            spare: budget - thresholds.iter().sum::<u32>(),
+            bag,
+            games,
+            score,
+        };
+    }
+}
+
+// Tries every way of splitting the budget between the colors. Only feasible for tiny budgets, but
+// it's obviously right.
+#[allow(dead_code)]
+fn brute_force(games: &[Game], budget: u32, objective: Objective) -> u64 {
+    fn splits(colors: usize, budget: u32) -> Vec<Vec<u32>> {
+        match colors {
+            0 => vec![vec![]],
+            1 => vec![vec![budget]],
+            _ => (0..=budget)
+                .flat_map(|n| {
+                    splits(colors - 1, budget - n)
+                        .into_iter()
+                        .map(move |mut rest| {
+                            rest.push(n);
+                            rest
+                        })
+                })
+                .collect(),
+        }
+    }
+
+    let colors = ["blue", "green", "red"];
+
+    splits(colors.len(), budget)
+        .into_iter()
+        .map(|split| {
+            let bag = ColorSet::parse(
+                &colors
+                    .iter()
+                    .zip(split)
+                    .map(|(name, n)| format!("{} {}", n, name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+            .unwrap();
+
+            games
+                .iter()
+                .filter(|game| game.check(&bag))
+                .map(|game| match objective {
+                    Objective::Count => 1,
+                    Objective::IdSum => game.id as u64,
+                })
+                .sum::<u64>()
+        })
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn test_optimise_example() {
+    let games = crate::parse(&common::day_file!("example")).unwrap();
+
+    let solution = optimise(&games, 48, Objective::Count);
+    assert_eq!(solution.bag.to_string(), "15 blue, 13 green, 20 red");
+    assert_eq!(solution.spare, 0);
+    assert_eq!(solution.games, vec![1, 2, 3, 4, 5]);
+    assert_eq!(solution.score, 5);
+
+    let solution = optimise(&games, 20, Objective::Count);
+    assert_eq!(solution.score, 3);
+    assert_eq!(solution.games, vec![1, 2, 5]);
+
+    let solution = optimise(&games, 0, Objective::Count);
+    assert_eq!(solution.score, 0);
+    assert!(solution.games.is_empty());
+}
+
+#[test]
+fn test_optimise_matches_brute_force() {
+    let games = crate::parse(&common::day_file!("example")).unwrap();
+
+    for objective in [Objective::Count, Objective::IdSum] {
+        for budget in 0..=50 {
+            let solution = optimise(&games, budget, objective);
+            assert_eq!(
+                solution.score,
+                brute_force(&games, budget, objective),
+                "budget {}",
+                budget
+            );
+
+            // The bag really does admit what it claims to, within budget
+            let admitted = games
+                .iter()
+                .filter(|game| solution.games.contains(&game.id))
+                .map(|game| match objective {
+                    Objective::Count => 1,
+                    Objective::IdSum => game.id as u64,
+                })
+                .sum::<u64>();
+            assert_eq!(admitted, solution.score);
+            assert_eq!(
+                solution.bag.counts.values().sum::<u32>() + solution.spare,
+                budget
+            );
+        }
+    }
+}
@@ -0,0 +1,257 @@
+// Explains why games are impossible for a bag: every draw that went over a limit, and for each
+// color how close the games came to (or how far past) its limit.
+
+use std::collections::BTreeMap;
+
+use common::json;
+
+use crate::{ColorSet, Game};
+
+// Rounds are numbered from 1, like games.
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    pub game: u32,
+    pub round: usize,
+    pub color: String,
+    pub drawn: u32,
+    pub limit: u32,
+}
+
+impl Violation {
+    pub fn excess(&self) -> u32 {
+        self.drawn - self.limit
+    }
+}
+
+// The largest draw of a color across every game, and where it happened first.
+#[derive(Debug, PartialEq)]
+pub struct Constraint {
+    pub color: String,
+    pub limit: u32,
+    pub max_drawn: u32,
+    pub game: u32,
+    pub round: usize,
+}
+
+impl Constraint {
+    // Negative when the limit is exceeded
+    pub fn slack(&self) -> i64 {
+        self.limit as i64 - self.max_drawn as i64
+    }
+}
+
+pub struct Report<'a> {
+    pub bag: &'a ColorSet,
+    pub violations: Vec<Violation>,
+    pub constraints: Vec<Constraint>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(games: &[Game], bag: &'a ColorSet) -> Self {
+        let mut violations = Vec::new();
+        let mut constraints: BTreeMap<&str, Constraint> = BTreeMap::new();
+
+        games.iter().for_each(|game| {
+            game.rounds.iter().enumerate().for_each(|(i, round)| {
+                round.iter().for_each(|color| {
+                    let limit = bag.get(&color.name);
+
+                    if !color.check(bag) {
+                        violations.push(Violation {
+                            game: game.id,
+                            round: i + 1,
+                            color: color.name.clone(),
+                            drawn: color.n,
+                            limit,
+                        });
+                    }
+
+                    let constraint =
+                        constraints
+                            .entry(color.name.as_str())
+                            .or_insert_with(|| Constraint {
+                                color: color.name.clone(),
+                                limit,
+                                max_drawn: 0,
+                                game: game.id,
+                                round: i + 1,
+                            });
+
+                    if color.n > constraint.max_drawn {
+                        constraint.max_drawn = color.n;
+                        constraint.game = game.id;
+                        constraint.round = i + 1;
+                    }
+                });
+            });
+        });
+
+        return Self {
+            bag,
+            violations,
+            constraints: constraints.into_values().collect(),
+        };
+    }
+
+    // The color with the least slack left, or the furthest over its limit.
+    pub fn tightest(&self) -> Option<&Constraint> {
+        self.constraints.iter().min_by_key(|c| c.slack())
+    }
+
+    pub fn table(&self) -> String {
+        let mut out = format!("Bag: {}\n", self.bag);
+
+        if self.violations.is_empty() {
+            out.push_str("Every game is possible\n");
+        } else {
+            out.push_str(&format!(
+                "{:>6} {:>6}  {:<12} {:>6} {:>6} {:>6}\n",
+                "Game", "Round", "Color", "Drawn", "Limit", "Excess"
+            ));
+            self.violations.iter().for_each(|v| {
+                out.push_str(&format!(
+                    "{:>6} {:>6}  {:<12} {:>6} {:>6} {:>6}\n",
+                    v.game,
+                    v.round,
+                    v.color,
+                    v.drawn,
+                    v.limit,
+                    v.excess()
+                ))
+            });
+        }
+
+        if let Some(c) = self.tightest() {
+            out.push_str(&format!(
+                "Tightest constraint: {}, limit {}, drawn up to {} (game {}, round {}), slack {}\n",
+                c.color,
+                c.limit,
+                c.max_drawn,
+                c.game,
+                c.round,
+                c.slack()
+            ));
+        }
+
+        return out;
+    }
+
+    // A single line of JSON
+    pub fn json(&self) -> String {
+        let bag = self
+            .bag
+            .counts
+            .iter()
+            .map(|(name, n)| format!("{}:{}", json::string(name), n))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let violations = self
+            .violations
+            .iter()
+            .map(|v| {
+                format!(
+                    "{{\"game\":{},\"round\":{},\"color\":{},\"drawn\":{},\"limit\":{},\"excess\":{}}}",
+                    v.game,
+                    v.round,
+                    json::string(&v.color),
+                    v.drawn,
+                    v.limit,
+                    v.excess()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let constraints = self
+            .constraints
+            .iter()
+            .map(|c| {
+                format!(
+                    "{{\"color\":{},\"limit\":{},\"max_drawn\":{},\"game\":{},\"round\":{},\"slack\":{}}}",
+                    json::string(&c.color),
+                    c.limit,
+                    c.max_drawn,
+                    c.game,
+                    c.round,
+                    c.slack()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let tightest = self
+            .tightest()
+            .map_or(String::from("null"), |c| json::string(&c.color));
+
+        return format!(
+            "{{\"bag\":{{{}}},\"violations\":[{}],\"constraints\":[{}],\"tightest\":{}}}",
+            bag, violations, constraints, tightest
+        );
+    }
+}
+
+#[test]
+fn test_violations() {
+    let games = crate::parse(&common::day_file!("example")).unwrap();
+    let bag = ColorSet::parse(crate::PUZZLE_BAG).unwrap();
+    let report = Report::new(&games, &bag);
+
+    assert_eq!(
+        report.violations,
+        vec![
+            Violation {
+                game: 3,
+                round: 1,
+                color: String::from("red"),
+                drawn: 20,
+                limit: 12
+            },
+            Violation {
+                game: 4,
+                round: 3,
+                color: String::from("blue"),
+                drawn: 15,
+                limit: 14
+            },
+            Violation {
+                game: 4,
+                round: 3,
+                color: String::from("red"),
+                drawn: 14,
+                limit: 12
+            },
+        ]
+    );
+    assert_eq!(report.violations[0].excess(), 8);
+
+    let tightest = report.tightest().unwrap();
+    assert_eq!(tightest.color, "red");
+    assert_eq!(tightest.slack(), -8);
+    assert_eq!((tightest.game, tightest.round), (3, 1));
+}
+
+#[test]
+fn test_output() {
+    let games = vec![Game::parse("Game 1: 3 blue, 4 \"red\"; 1 blue").unwrap()];
+    let bag = ColorSet::parse("3 blue").unwrap();
+    let report = Report::new(&games, &bag);
+
+    assert_eq!(
+        report.table(),
+        "Bag: 3 blue\n\
+         \x20 Game  Round  Color         Drawn  Limit Excess\n\
+         \x20    1      1  \"red\"             4      0      4\n\
+         Tightest constraint: \"red\", limit 0, drawn up to 4 (game 1, round 1), slack -4\n"
+    );
+
+    assert_eq!(
+        report.json(),
+        "{\"bag\":{\"blue\":3},\
+         \"violations\":[{\"game\":1,\"round\":1,\"color\":\"\\\"red\\\"\",\"drawn\":4,\"limit\":0,\"excess\":4}],\
+         \"constraints\":[\
+         {\"color\":\"\\\"red\\\"\",\"limit\":0,\"max_drawn\":4,\"game\":1,\"round\":1,\"slack\":-4},\
+         {\"color\":\"blue\",\"limit\":3,\"max_drawn\":3,\"game\":1,\"round\":1,\"slack\":0}],\
+         \"tightest\":\"\\\"red\\\"\"}"
+    );
+}
@@ -0,0 +1,63 @@
+// Engine schematics: part numbers and symbols scattered over a grid of '.'.
+
+use common::gen::{Param, Rng, Sizes};
+
+const SYMBOLS: &[char] = &['*', '#', '+', '$', '/', '@', '%', '=', '&', '-'];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "rows",
+        default: 140,
+        help: "schematic height",
+    },
+    Param {
+        name: "cols",
+        default: 140,
+        help: "schematic width",
+    },
+    Param {
+        name: "numbers",
+        default: 12,
+        help: "chance in 100 of a number starting at each free cell",
+    },
+    Param {
+        name: "symbols",
+        default: 5,
+        help: "chance in 100 of a symbol at each free cell",
+    },
+];
+
+pub fn generate(rng: &mut Rng, sizes: &Sizes) -> String {
+    let cols = sizes.get("cols").max(1) as usize;
+
+    (0..sizes.get("rows"))
+        .map(|_| {
+            let mut row = String::with_capacity(cols + 1);
+            let mut col = 0;
+
+            while col < cols {
+                if rng.chance(sizes.get("numbers")) {
+                    // Numbers never touch the next thing on their row, or they'd run into it
+                    let digits = (rng.range(1, 3) as usize).min(cols - col);
+                    row.push(char::from(b'1' + rng.range(0, 8) as u8));
+                    (1..digits).for_each(|_| row.push(char::from(b'0' + rng.range(0, 9) as u8)));
+                    col += digits;
+
+                    if col < cols {
+                        row.push('.');
+                        col += 1;
+                    }
+                    continue;
+                }
+
+                match rng.chance(sizes.get("symbols")) {
+                    true => row.push(*rng.pick(SYMBOLS)),
+                    false => row.push('.'),
+                }
+                col += 1;
+            }
+
+            row + "\n"
+        })
+        .collect()
+}
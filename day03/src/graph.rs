@@ -0,0 +1,257 @@
+// Bipartite graph of part numbers and the symbols they touch, for seeing how parts cluster into
+// assemblies beyond the two-part gears the puzzle asks about.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::grid::Grid;
+use crate::rules::Rules;
+use crate::{neighbours, PartNo};
+
+pub struct Graph<'a> {
+    parts: &'a [PartNo],
+    symbols: Vec<((usize, usize), char)>,
+    // Part index to the indices of the symbols it touches, and vice versa
+    part_edges: Vec<Vec<usize>>,
+    symbol_edges: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Node {
+    Part(usize),
+    Symbol(usize),
+}
+
+// Indices into the graph's parts and symbols, both in reading order.
+#[derive(Debug, PartialEq)]
+pub struct Component {
+    pub parts: Vec<usize>,
+    pub symbols: Vec<usize>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new(parts: &'a [PartNo], grid: &Grid, rules: &Rules) -> Self {
+        let mut symbols = grid
+            .symbols()
+            .iter()
+            .map(|(&position, &c)| (position, c))
+            .collect::<Vec<_>>();
+        symbols.sort();
+
+        let index = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, (position, _))| (*position, i))
+            .collect::<HashMap<_, _>>();
+
+        let mut part_edges = vec![Vec::new(); parts.len()];
+        let mut symbol_edges = vec![Vec::new(); symbols.len()];
+
+        parts.iter().enumerate().for_each(|(p, part_no)| {
+            neighbours(part_no, grid, rules.connectivity)
+                .filter_map(|position| index.get(&position))
+                .for_each(|&s| {
+                    part_edges[p].push(s);
+                    symbol_edges[s].push(p);
+                });
+        });
+
+        return Self {
+            parts,
+            symbols,
+            part_edges,
+            symbol_edges,
+        };
+    }
+
+    pub fn part(&self, index: usize) -> &PartNo {
+        &self.parts[index]
+    }
+
+    pub fn symbol(&self, index: usize) -> ((usize, usize), char) {
+        self.symbols[index]
+    }
+
+    fn edges(&self, node: Node) -> impl Iterator<Item = Node> + '_ {
+        let (edges, to): (&Vec<usize>, fn(usize) -> Node) = match node {
+            Node::Part(p) => (&self.part_edges[p], Node::Symbol),
+            Node::Symbol(s) => (&self.symbol_edges[s], Node::Part),
+        };
+
+        edges.iter().map(move |&i| to(i))
+    }
+
+    // Every connected group of parts and symbols, including parts touching nothing and symbols
+    // touching no parts.
+    pub fn components(&self) -> Vec<Component> {
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+
+        let nodes = (0..self.parts.len())
+            .map(Node::Part)
+            .chain((0..self.symbols.len()).map(Node::Symbol));
+
+        for start in nodes {
+            if seen.contains(&start) {
+                continue;
+            }
+
+            let mut component = Component {
+                parts: Vec::new(),
+                symbols: Vec::new(),
+            };
+            let mut queue = VecDeque::from([start]);
+            seen.insert(start);
+
+            while let Some(node) = queue.pop_front() {
+                match node {
+                    Node::Part(p) => component.parts.push(p),
+                    Node::Symbol(s) => component.symbols.push(s),
+                }
+
+                self.edges(node).for_each(|next| {
+                    if seen.insert(next) {
+                        queue.push_back(next);
+                    }
+                });
+            }
+
+            component.parts.sort();
+            component.symbols.sort();
+            components.push(component);
+        }
+
+        return components;
+    }
+
+    // The component with the most parts, then the most symbols. Ties go to whichever comes first
+    // in reading order.
+    pub fn largest_assembly(&self) -> Option<Component> {
+        let mut components = self.components();
+        let (i, _) = components.iter().enumerate().min_by_key(|(_, c)| {
+            (
+                std::cmp::Reverse(c.parts.len()),
+                std::cmp::Reverse(c.symbols.len()),
+            )
+        })?;
+
+        Some(components.swap_remove(i))
+    }
+
+    pub fn lonely_symbols(&self) -> Vec<usize> {
+        (0..self.symbols.len())
+            .filter(|&s| self.symbol_edges[s].is_empty())
+            .collect()
+    }
+
+    pub fn shared_parts(&self) -> Vec<usize> {
+        (0..self.parts.len())
+            .filter(|&p| self.part_edges[p].len() > 1)
+            .collect()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("graph schematic {\n");
+
+        self.parts.iter().enumerate().for_each(|(p, part_no)| {
+            out.push_str(&format!(
+                "    p{} [label=\"{}\" shape=box tooltip=\"{},{}\"];\n",
+                p, part_no.digits, part_no.row, part_no.col
+            ));
+        });
+
+        self.symbols
+            .iter()
+            .enumerate()
+            .for_each(|(s, ((row, col), c))| {
+                out.push_str(&format!(
+                    "    s{} [label=\"{}\" shape=circle tooltip=\"{},{}\"];\n",
+                    s,
+                    escape(*c),
+                    row,
+                    col
+                ));
+            });
+
+        self.part_edges.iter().enumerate().for_each(|(p, edges)| {
+            edges
+                .iter()
+                .for_each(|s| out.push_str(&format!("    p{} -- s{};\n", p, s)));
+        });
+
+        out.push_str("}\n");
+
+        return out;
+    }
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '"' | '\\' => format!("\\{}", c),
+        c => c.to_string(),
+    }
+}
+
+#[allow(dead_code)]
+fn with_graph(text: &str, f: impl FnOnce(&Graph)) {
+    use crate::extract_numbers;
+
+    let rules = Rules::default();
+    let grid = Grid::parse(text, &rules.symbols).unwrap();
+    let parts = extract_numbers(&grid).unwrap();
+
+    f(&Graph::new(&parts, &grid, &rules));
+}
+
+#[test]
+fn test_components() {
+    let text = common::day_file!("example");
+
+    with_graph(&text, |graph| {
+        let components = graph.components();
+        assert_eq!(components.len(), 8);
+
+        // 467 and 35 share a gear
+        assert_eq!(components[0].parts, vec![0, 2]);
+        assert_eq!(components[0].symbols.len(), 1);
+
+        // 114 touches nothing
+        assert_eq!(components[1].parts, vec![1]);
+        assert!(components[1].symbols.is_empty());
+
+        let largest = graph.largest_assembly().unwrap();
+        assert_eq!(largest, components.into_iter().next().unwrap());
+
+        assert!(graph.lonely_symbols().is_empty());
+        assert!(graph.shared_parts().is_empty());
+    });
+}
+
+#[test]
+fn test_lonely_symbols_and_shared_parts() {
+    with_graph("12..$\n*#...\n3....", |graph| {
+        assert_eq!(graph.lonely_symbols(), vec![0]);
+        assert_eq!(graph.symbol(0), ((0, 4), '$'));
+
+        assert_eq!(graph.shared_parts(), vec![0, 1]);
+        assert_eq!(graph.part(0).digits, 12);
+
+        let largest = graph.largest_assembly().unwrap();
+        assert_eq!(largest.parts, vec![0, 1]);
+        assert_eq!(largest.symbols, vec![1, 2]);
+    });
+}
+
+#[test]
+fn test_to_dot() {
+    with_graph("12.\"\n*...", |graph| {
+        assert_eq!(
+            graph.to_dot(),
+            "graph schematic {\n\
+             \x20   p0 [label=\"12\" shape=box tooltip=\"0,0\"];\n\
+             \x20   s0 [label=\"\\\"\" shape=circle tooltip=\"0,3\"];\n\
+             \x20   s1 [label=\"*\" shape=circle tooltip=\"1,0\"];\n\
+             \x20   p0 -- s1;\n\
+             }\n"
+        );
+    });
+}
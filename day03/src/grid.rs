@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use common::ParseError;
+
+use crate::rules::Symbols;
+
+// The schematic, parsed once up front. Cells are stored row by row so any position can be looked
+// up directly, and every symbol is indexed by its (row, col) position.
+#[derive(Debug)]
+pub struct Grid {
+    cells: Vec<char>,
+    width: usize,
+    height: usize,
+    symbols: HashMap<(usize, usize), char>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    Empty,
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    // A part number too large to hold
+    TooLarge {
+        row: usize,
+        col: usize,
+    },
+    // A row which couldn't be read, such as one that isn't UTF-8
+    Unreadable {
+        row: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "schematic is empty"),
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} is {} columns wide, expected {}",
+                row, found, expected
+            ),
+            GridError::TooLarge { row, col } => {
+                write!(f, "part number at row {} column {} is too large", row, col)
+            }
+            GridError::Unreadable { row, message } => {
+                write!(f, "row {} couldn't be read, {}", row, message)
+            }
+        }
+    }
+}
+
+// Rows are counted from 0 but lines from 1
+impl From<GridError> for ParseError {
+    fn from(e: GridError) -> Self {
+        match e {
+            GridError::Empty => ParseError::new(1, "schematic is empty"),
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => ParseError::new(
+                row + 1,
+                &format!("{} columns wide, expected {}", found, expected),
+            ),
+            GridError::TooLarge { row, col } => ParseError::new(
+                row + 1,
+                &format!("part number at column {} is too large", col),
+            ),
+            GridError::Unreadable { row, message } => ParseError::new(row + 1, &message),
+        }
+    }
+}
+
+impl Grid {
+    pub fn parse(text: &str, symbol_set: &Symbols) -> Result<Self, GridError> {
+        let rows: Vec<_> = text.trim().lines().collect();
+
+        return Self::from_rows(&rows, symbol_set);
+    }
+
+    pub fn from_rows(rows: &[&str], symbol_set: &Symbols) -> Result<Self, GridError> {
+        let width = rows.first().ok_or(GridError::Empty)?.chars().count();
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+        let mut symbols = HashMap::new();
+
+        for (row_no, row) in rows.iter().enumerate() {
+            let found = row.chars().count();
+            if found != width {
+                return Err(GridError::Ragged {
+                    row: row_no,
+                    expected: width,
+                    found,
+                });
+            }
+
+            row.chars().enumerate().for_each(|(col_no, c)| {
+                if symbol_set.contains(c) {
+                    symbols.insert((row_no, col_no), c);
+                }
+                cells.push(c);
+            });
+        }
+
+        return Ok(Self {
+            cells,
+            width,
+            height: rows.len(),
+            symbols,
+        });
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<char> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+
+        Some(self.cells[row * self.width + col])
+    }
+
+    pub fn row(&self, row: usize) -> &[char] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn symbol_at(&self, row: usize, col: usize) -> Option<char> {
+        self.symbols.get(&(row, col)).copied()
+    }
+
+    pub fn symbols(&self) -> &HashMap<(usize, usize), char> {
+        &self.symbols
+    }
+}
+
+#[test]
+fn test_parse() {
+    use crate::rules::Rules;
+
+    let grid = Grid::parse("467..\n...*.\n..35#\n", &Rules::default().symbols).unwrap();
+
+    assert_eq!(grid.width(), 5);
+    assert_eq!(grid.height(), 3);
+    assert_eq!(grid.get(0, 0), Some('4'));
+    assert_eq!(grid.get(2, 4), Some('#'));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 5), None);
+
+    assert_eq!(grid.symbols().len(), 2);
+    assert_eq!(grid.symbol_at(1, 3), Some('*'));
+    assert_eq!(grid.symbol_at(0, 0), None);
+}
+
+#[test]
+fn test_parse_rejects_ragged_rows() {
+    use crate::rules::Rules;
+
+    assert_eq!(
+        Grid::parse("467..\n...*\n..35#", &Rules::default().symbols).unwrap_err(),
+        GridError::Ragged {
+            row: 1,
+            expected: 5,
+            found: 4
+        }
+    );
+    assert_eq!(
+        Grid::parse("\n\n", &Rules::default().symbols).unwrap_err(),
+        GridError::Empty
+    );
+}
+
+#[test]
+fn test_parse_multibyte() {
+    use crate::rules::Rules;
+
+    let grid = Grid::parse("§.★\n1.2", &Rules::default().symbols).unwrap();
+
+    assert_eq!(grid.width(), 3);
+    assert_eq!(grid.row(0), &['§', '.', '★']);
+    assert_eq!(grid.symbol_at(0, 2), Some('★'));
+    assert_eq!(grid.get(1, 2), Some('2'));
+
+    assert_eq!(
+        Grid::parse("§.★\n1.", &Rules::default().symbols).unwrap_err(),
+        GridError::Ragged {
+            row: 1,
+            expected: 3,
+            found: 2
+        }
+    );
+}
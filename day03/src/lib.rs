@@ -0,0 +1,537 @@
+#![allow(clippy::needless_return)]
+
+use std::collections::HashMap;
+
+use common::bench::Bencher;
+use common::input::Source;
+use common::{answer, checked_product, checked_sum, par, Error, Num, Overflow, ParseError};
+use grid::{Grid, GridError};
+use rules::{Connectivity, Rules};
+
+pub mod gen;
+mod graph;
+mod grid;
+mod render;
+pub mod rules;
+mod stream;
+
+// Reads the one input a subcommand takes, after its flags
+fn read_input(args: &[String], usage: &str) -> String {
+    let source = match Source::from_args(args).as_deref() {
+        Ok([source]) => source.read(),
+        _ => Err(String::from(usage)),
+    };
+
+    source.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
+
+fn parse_or_exit(text: &str, rules: &Rules) -> (Grid, Vec<PartNo>) {
+    parse(text, rules).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
+
+// render [--ansi|--html|--svg] <filename|-|--text schematic>
+pub fn render(args: &[String], rules: &Rules) {
+    use render::Format;
+
+    let usage = "usage: render [--ansi|--html|--svg] <filename|-|--text schematic>";
+    let (format, text) = match args.first().map(String::as_str) {
+        Some("--ansi") => (Format::Ansi, read_input(&args[1..], usage)),
+        Some("--html") => (Format::Html, read_input(&args[1..], usage)),
+        Some("--svg") => (Format::Svg, read_input(&args[1..], usage)),
+        _ => (Format::Ansi, read_input(args, usage)),
+    };
+
+    let (grid, parts) = parse_or_exit(&text, rules);
+    let gears = extract_gears(&parts, &grid, rules);
+    let annotated = render::Annotated::new(&grid, &parts, &gears, rules);
+
+    match format {
+        Format::Ansi => print!("{}", annotated.ansi()),
+        Format::Html => print!("{}", annotated.html()),
+        Format::Svg => print!("{}", annotated.svg()),
+    }
+}
+
+// graph [--dot] <filename|-|--text schematic>
+pub fn graph(args: &[String], rules: &Rules) {
+    let usage = "usage: graph [--dot] <filename|-|--text schematic>";
+    let (dot, text) = match args.first().map(String::as_str) {
+        Some("--dot") => (true, read_input(&args[1..], usage)),
+        _ => (false, read_input(args, usage)),
+    };
+
+    let (grid, parts) = parse_or_exit(&text, rules);
+    let graph = graph::Graph::new(&parts, &grid, rules);
+
+    if dot {
+        return print!("{}", graph.to_dot());
+    }
+
+    let describe_symbol = |s| {
+        let ((row, col), c) = graph.symbol(s);
+        format!("{} at {},{}", c, row, col)
+    };
+    let describe_part = |p| {
+        let part_no = graph.part(p);
+        format!("{} at {},{}", part_no.digits, part_no.row, part_no.col)
+    };
+
+    println!("Components: {}", graph.components().len());
+
+    if let Some(largest) = graph.largest_assembly() {
+        println!(
+            "Largest assembly: {} parts, {} symbols",
+            largest.parts.len(),
+            largest.symbols.len()
+        );
+        largest
+            .parts
+            .iter()
+            .for_each(|&p| println!("  part {}", describe_part(p)));
+        largest
+            .symbols
+            .iter()
+            .for_each(|&s| println!("  symbol {}", describe_symbol(s)));
+    }
+
+    println!("Symbols without parts:");
+    graph
+        .lonely_symbols()
+        .into_iter()
+        .for_each(|s| println!("  {}", describe_symbol(s)));
+
+    println!("Parts touching several symbols:");
+    graph
+        .shared_parts()
+        .into_iter()
+        .for_each(|p| println!("  {}", describe_part(p)));
+}
+
+// stream [--events] <filename|-|--text schematic>
+pub fn stream<N: Num>(args: &[String], rules: &Rules) {
+    let (events, args) = match args.first().map(String::as_str) {
+        Some("--events") => (true, &args[1..]),
+        _ => (false, args),
+    };
+
+    let reader = match Source::from_args(args).as_deref() {
+        Ok([source]) => source.reader(),
+        _ => Err(String::from(
+            "usage: stream [--events] <filename|-|--text schematic>",
+        )),
+    };
+    let reader = reader.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    let mut totals = stream::Totals::<N>::new();
+
+    let result = stream::scan(reader, rules, |event| {
+        if events {
+            println!("{:?}", event);
+        }
+
+        totals.add(&event);
+    });
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
+    println!("Part one: {}", answer(totals.part_sum));
+    println!("Part two: {}", answer(totals.ratio_sum));
+}
+
+pub fn part_one<N: Num>(text: &str, rules: &Rules) -> Result<N, Error> {
+    let (grid, parts) = parse(text, rules)?;
+
+    return Ok(part_number_sum(&grid, &parts, rules)?);
+}
+
+pub fn part_two<N: Num>(text: &str, rules: &Rules) -> Result<N, Error> {
+    let (grid, parts) = parse(text, rules)?;
+
+    return Ok(gear_ratio_sum(&grid, &parts, rules)?);
+}
+
+// Times parsing and solving separately, for `aoc bench`
+pub fn bench(text: &str, b: &mut Bencher) -> Result<(), Error> {
+    let rules = Rules::default();
+
+    let (grid, parts) = b.run("parse", || parse(text, &rules))?;
+    b.run("part one", || part_number_sum::<u64>(&grid, &parts, &rules))?;
+    b.run("part two", || gear_ratio_sum::<u64>(&grid, &parts, &rules))?;
+
+    return Ok(());
+}
+
+fn parse(text: &str, rules: &Rules) -> Result<(Grid, Vec<PartNo>), ParseError> {
+    let grid = Grid::parse(text, &rules.symbols)?;
+    let parts = extract_numbers(&grid)?;
+
+    return Ok((grid, parts));
+}
+
+fn part_number_sum<N: Num>(grid: &Grid, parts: &[PartNo], rules: &Rules) -> Result<N, Overflow> {
+    let valid = par::map(parts, |p| is_valid(p, grid, rules));
+
+    return checked_sum(
+        parts
+            .iter()
+            .zip(valid)
+            .filter(|(_, valid)| *valid)
+            .map(|(p, _)| N::from_u64(p.digits as u64)),
+        "the part number sum",
+    );
+}
+
+fn gear_ratio_sum<N: Num>(grid: &Grid, parts: &[PartNo], rules: &Rules) -> Result<N, Overflow> {
+    let gears = extract_gears(parts, grid, rules);
+
+    return sum_ratios(&gears, rules);
+}
+
+fn sum_ratios<N: Num>(
+    gears: &HashMap<(usize, usize), Vec<usize>>,
+    rules: &Rules,
+) -> Result<N, Overflow> {
+    let ratios = gears
+        .iter()
+        .filter(|(_, g)| rules.gear_parts.matches(g.len()))
+        .map(|(_, g)| gear_ratio(g))
+        .collect::<Result<Vec<N>, _>>()?;
+
+    return checked_sum(ratios, "the gear ratio sum");
+}
+
+fn gear_ratio<N: Num>(parts: &[usize]) -> Result<N, Overflow> {
+    checked_product(
+        parts.iter().map(|&digits| N::from_u64(digits as u64)),
+        "a gear ratio",
+    )
+}
+
+#[derive(Debug, PartialEq)]
+struct PartNo {
+    digits: usize,
+    row: usize,
+    col: usize,
+    // Number of columns the digits take up, leading zeros included
+    width: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Bounds {
+    start_row: usize,
+    end_row: usize,
+    start_col: usize,
+    end_col: usize,
+}
+
+impl Bounds {
+    fn cells(self) -> impl Iterator<Item = (usize, usize)> {
+        (self.start_row..=self.end_row)
+            .flat_map(move |row| (self.start_col..=self.end_col).map(move |col| (row, col)))
+    }
+}
+
+// Cells around a part number which count as touching it. The part's own digits are skipped, and
+// with 4-connectivity so are the corners diagonal to its first and last digits.
+fn neighbours<'a>(
+    part_no: &'a PartNo,
+    grid: &Grid,
+    connectivity: Connectivity,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    let first_col = part_no.col;
+    let last_col = part_no.col + part_no.width - 1;
+
+    get_bounds(part_no, grid)
+        .cells()
+        .filter(move |&(row, col)| {
+            let beside = col < first_col || col > last_col;
+
+            match (row == part_no.row, connectivity) {
+                (true, _) => beside,
+                (false, Connectivity::Eight) => true,
+                (false, Connectivity::Four) => !beside,
+            }
+        })
+}
+
+fn get_bounds(part_no: &PartNo, grid: &Grid) -> Bounds {
+    let start_row = part_no.row.saturating_sub(1);
+    let end_row = std::cmp::min(part_no.row + 1, grid.height() - 1);
+
+    let start_col = part_no.col.saturating_sub(1);
+    let end_col = std::cmp::min(grid.width() - 1, part_no.col + part_no.width);
+
+    return Bounds {
+        start_row,
+        end_row,
+        start_col,
+        end_col,
+    };
+}
+
+fn is_valid(part_no: &PartNo, grid: &Grid, rules: &Rules) -> bool {
+    neighbours(part_no, grid, rules.connectivity)
+        .any(|(row, col)| grid.symbol_at(row, col).is_some())
+}
+
+// Columns are counted in chars, the same as the grid, so multibyte symbols take up one column
+// like any other. Numbers don't span rows, so each row is read on its own thread.
+fn extract_numbers(grid: &Grid) -> Result<Vec<PartNo>, GridError> {
+    let rows = (0..grid.height()).collect::<Vec<_>>();
+    let mut parts = Vec::new();
+
+    for row in par::map(&rows, |&line_no| row_numbers(grid, line_no)) {
+        parts.extend(row?);
+    }
+
+    return Ok(parts);
+}
+
+fn row_numbers(grid: &Grid, line_no: usize) -> Result<Vec<PartNo>, GridError> {
+    let mut current_digits: Option<(usize, String)> = None;
+    let mut parts: Vec<PartNo> = Vec::new();
+
+    for (col_no, &c) in grid.row(line_no).iter().enumerate() {
+        match (c.is_ascii_digit(), current_digits.clone()) {
+            (true, None) => current_digits = Some((col_no, c.to_string())),
+            (true, Some((i, mut existing))) => {
+                existing.push(c);
+                current_digits = Some((i, existing));
+            }
+            (false, Some((i, existing))) => {
+                parts.push(part_no(line_no, i, &existing)?);
+                current_digits = None;
+            }
+            (false, None) => (),
+        }
+    }
+
+    // End of line
+    if let Some((i, existing)) = current_digits {
+        parts.push(part_no(line_no, i, &existing)?);
+    }
+
+    return Ok(parts);
+}
+
+fn part_no(row: usize, col: usize, digits: &str) -> Result<PartNo, GridError> {
+    Ok(PartNo {
+        digits: digits
+            .parse()
+            .map_err(|_| GridError::TooLarge { row, col })?,
+        row,
+        col,
+        width: digits.len(),
+    })
+}
+
+// HashMap keys are X,Y positions of gears (star characters by default), values are a vector of
+// part numbers touching.
+fn extract_gears(
+    parts: &[PartNo],
+    grid: &Grid,
+    rules: &Rules,
+) -> HashMap<(usize, usize), Vec<usize>> {
+    let mut gears: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+
+    parts.iter().for_each(|part_no| {
+        neighbours(part_no, grid, rules.connectivity)
+            .filter(|&(row, col)| grid.get(row, col) == Some(rules.gear))
+            .for_each(|position| {
+                gears
+                    .entry(position)
+                    .and_modify(|e| e.push(part_no.digits))
+                    .or_insert(vec![part_no.digits]);
+            });
+    });
+
+    return gears;
+}
+
+#[test]
+fn test_part_one() {
+    use common::{day_file, BigUint};
+
+    assert_eq!(
+        part_one::<u64>(&day_file!("example"), &Rules::default()),
+        Ok(4361)
+    );
+    assert_eq!(
+        part_one::<u64>(&day_file!("input"), &Rules::default()),
+        Ok(531561)
+    );
+    assert_eq!(
+        part_one::<BigUint>(&day_file!("input"), &Rules::default()),
+        Ok(BigUint::from(531561))
+    );
+}
+
+#[test]
+fn test_part_two() {
+    use common::{day_file, BigUint};
+
+    assert_eq!(
+        part_two::<u64>(&day_file!("example"), &Rules::default()),
+        Ok(467835)
+    );
+    assert_eq!(
+        part_two::<u64>(&day_file!("input"), &Rules::default()),
+        Ok(83279367)
+    );
+    assert_eq!(
+        part_two::<BigUint>(&day_file!("input"), &Rules::default()),
+        Ok(BigUint::from(83279367))
+    );
+}
+
+#[test]
+fn test_four_connectivity() {
+    let rules = Rules {
+        connectivity: Connectivity::Four,
+        ..Rules::default()
+    };
+    let text = "*..#\n.12.\n....\n#3..";
+    let grid = Grid::parse(text, &rules.symbols).unwrap();
+    let parts = extract_numbers(&grid).unwrap();
+
+    // Only diagonal to a symbol
+    assert!(!is_valid(&parts[0], &grid, &rules));
+    assert!(is_valid(&parts[0], &grid, &Rules::default()));
+
+    // Beside one
+    assert!(is_valid(&parts[1], &grid, &rules));
+}
+
+#[test]
+fn test_gear_rules() {
+    let text = "2.3\n.$.\n4.5";
+    let grid = Grid::parse(text, &Rules::default().symbols).unwrap();
+    let parts = extract_numbers(&grid).unwrap();
+
+    let rules = Rules {
+        gear: '$',
+        ..Rules::default()
+    };
+    let gears = extract_gears(&parts, &grid, &rules);
+    assert_eq!(gears.get(&(1, 1)).unwrap().len(), 4);
+    assert!(extract_gears(&parts, &grid, &Rules::default()).is_empty());
+
+    let rules = Rules {
+        gear: '$',
+        gear_parts: rules::PartCount::AtLeast(2),
+        ..Rules::default()
+    };
+    assert_eq!(sum_ratios::<u64>(&gears, &rules), Ok(120));
+}
+
+#[test]
+fn test_multibyte_symbols() {
+    let text = "★12.§\n....7\n§...★\n..007";
+    let rules = Rules {
+        gear: '★',
+        gear_parts: rules::PartCount::AtLeast(1),
+        ..Rules::default()
+    };
+    let grid = Grid::parse(text, &rules.symbols).unwrap();
+    let parts = extract_numbers(&grid).unwrap();
+
+    assert_eq!(
+        parts,
+        vec![
+            PartNo {
+                digits: 12,
+                row: 0,
+                col: 1,
+                width: 2
+            },
+            PartNo {
+                digits: 7,
+                row: 1,
+                col: 4,
+                width: 1
+            },
+            PartNo {
+                digits: 7,
+                row: 3,
+                col: 2,
+                width: 3
+            },
+        ]
+    );
+
+    assert!(parts.iter().all(|p| is_valid(p, &grid, &rules)));
+
+    let gears = extract_gears(&parts, &grid, &rules);
+    assert_eq!(gears.get(&(0, 0)), Some(&vec![12]));
+    assert_eq!(gears.get(&(2, 4)), Some(&vec![7, 7]));
+    assert_eq!(sum_ratios::<u64>(&gears, &rules), Ok(61));
+}
+
+#[test]
+fn test_gear_ratio_overflow() {
+    use common::BigUint;
+
+    let parts = [9_999_999_999, 9_999_999_999];
+
+    assert!(gear_ratio::<u64>(&parts).is_err());
+    assert_eq!(
+        gear_ratio::<BigUint>(&parts).unwrap().to_string(),
+        "99999999980000000001"
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    let rules = Rules::default();
+    let error = |line, message: &str| Err(ParseError::new(line, message).into());
+
+    assert_eq!(part_one::<u64>("", &rules), error(1, "schematic is empty"));
+    assert_eq!(
+        part_one::<u64>("12.\n.*.\n3", &rules),
+        error(3, "1 columns wide, expected 3")
+    );
+    assert_eq!(
+        part_two::<u64>(&format!("{}\n*{}", ".".repeat(31), "9".repeat(30)), &rules),
+        error(2, "part number at column 1 is too large")
+    );
+}
+
+#[test]
+fn test_generated_input() {
+    use crate::gen::{generate, PARAMS};
+    use common::gen::{Rng, Sizes};
+
+    let text = generate(&mut Rng::new(1), &Sizes::defaults(PARAMS));
+    let grid = Grid::parse(&text, &Rules::default().symbols).unwrap();
+
+    assert_eq!((grid.height(), grid.width()), (140, 140));
+    text.lines().enumerate().for_each(|(row, line)| {
+        assert_eq!(grid.row(row).iter().collect::<String>(), line);
+    });
+
+    // Every number comes back out at the place it was written
+    let parts = extract_numbers(&grid).unwrap();
+    assert!(!parts.is_empty());
+    parts.iter().for_each(|p| {
+        let written = grid.row(p.row)[p.col..p.col + p.width]
+            .iter()
+            .collect::<String>();
+        assert_eq!(written, p.digits.to_string());
+    });
+
+    let digits = text.chars().filter(char::is_ascii_digit).count();
+    let width = parts.iter().map(|p| p.width).sum::<usize>();
+    assert_eq!(digits, width);
+}
@@ -0,0 +1,307 @@
+// Renders a schematic back out with its analysis marked up: part numbers touching a symbol,
+// orphaned numbers that don't, and gears. Mostly useful for eyeballing adjacency at the edges of
+// the grid.
+
+use std::collections::HashMap;
+
+use crate::grid::Grid;
+use crate::rules::Rules;
+use crate::{is_valid, PartNo};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    // Colored for a terminal
+    Ansi,
+    Html,
+    Svg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mark {
+    Plain,
+    ValidPart,
+    OrphanPart,
+    Symbol,
+    Gear,
+}
+
+impl Mark {
+    fn ansi(&self) -> Option<&'static str> {
+        match self {
+            Mark::Plain => None,
+            Mark::ValidPart => Some("\x1b[1;32m"),
+            Mark::OrphanPart => Some("\x1b[1;31m"),
+            Mark::Symbol => Some("\x1b[1;36m"),
+            Mark::Gear => Some("\x1b[1;30;43m"),
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        match self {
+            Mark::Plain => "plain",
+            Mark::ValidPart => "valid",
+            Mark::OrphanPart => "orphan",
+            Mark::Symbol => "symbol",
+            Mark::Gear => "gear",
+        }
+    }
+
+    fn colour(&self) -> &'static str {
+        match self {
+            Mark::Plain => "#888888",
+            Mark::ValidPart => "#2e9e44",
+            Mark::OrphanPart => "#d23c3c",
+            Mark::Symbol => "#2a8fbd",
+            Mark::Gear => "#d9a400",
+        }
+    }
+}
+
+// One mark per cell, row by row.
+pub struct Annotated<'a> {
+    grid: &'a Grid,
+    marks: Vec<Mark>,
+}
+
+impl<'a> Annotated<'a> {
+    pub fn new(
+        grid: &'a Grid,
+        parts: &[PartNo],
+        gears: &HashMap<(usize, usize), Vec<usize>>,
+        rules: &Rules,
+    ) -> Self {
+        let mut marks = vec![Mark::Plain; grid.width() * grid.height()];
+
+        grid.symbols().keys().for_each(|(row, col)| {
+            marks[row * grid.width() + col] = Mark::Symbol;
+        });
+
+        gears
+            .iter()
+            .filter(|(_, g)| rules.gear_parts.matches(g.len()))
+            .for_each(|((row, col), _)| marks[row * grid.width() + col] = Mark::Gear);
+
+        parts.iter().for_each(|part_no| {
+            let mark = match is_valid(part_no, grid, rules) {
+                true => Mark::ValidPart,
+                false => Mark::OrphanPart,
+            };
+
+            (part_no.col..part_no.col + part_no.width)
+                .for_each(|col| marks[part_no.row * grid.width() + col] = mark);
+        });
+
+        return Self { grid, marks };
+    }
+
+    pub fn mark(&self, row: usize, col: usize) -> Mark {
+        self.marks[row * self.grid.width() + col]
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (usize, usize, char, Mark)> + '_ {
+        (0..self.grid.height()).flat_map(move |row| {
+            (0..self.grid.width()).map(move |col| {
+                (
+                    row,
+                    col,
+                    self.grid.get(row, col).unwrap(),
+                    self.mark(row, col),
+                )
+            })
+        })
+    }
+
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+
+        self.cells().for_each(|(_, col, c, mark)| {
+            match mark.ansi() {
+                Some(code) => out.push_str(&format!("{}{}\x1b[0m", code, c)),
+                None => out.push(c),
+            }
+
+            if col == self.grid.width() - 1 {
+                out.push('\n');
+            }
+        });
+
+        return out;
+    }
+
+    pub fn html(&self) -> String {
+        let mut body = String::new();
+
+        self.cells().for_each(|(_, col, c, mark)| {
+            match mark {
+                Mark::Plain => body.push_str(&escape(c)),
+                _ => body.push_str(&format!(
+                    "<span class=\"{}\">{}</span>",
+                    mark.class(),
+                    escape(c)
+                )),
+            }
+
+            if col == self.grid.width() - 1 {
+                body.push('\n');
+            }
+        });
+
+        let styles = [
+            Mark::Plain,
+            Mark::ValidPart,
+            Mark::OrphanPart,
+            Mark::Symbol,
+            Mark::Gear,
+        ]
+        .iter()
+        .map(|mark| format!(".{} {{ color: {}; }}", mark.class(), mark.colour()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        return format!(
+            "<!DOCTYPE html>\n\
+             <html>\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <title>Schematic</title>\n\
+             <style>\n\
+             body {{ background: #111111; color: {}; }}\n\
+             {}\n\
+             .gear {{ font-weight: bold; }}\n\
+             </style>\n\
+             </head>\n\
+             <body>\n\
+             <pre>\n{}</pre>\n\
+             </body>\n\
+             </html>\n",
+            Mark::Plain.colour(),
+            styles,
+            body
+        );
+    }
+
+    pub fn svg(&self) -> String {
+        const CELL_WIDTH: usize = 10;
+        const CELL_HEIGHT: usize = 16;
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             font-family=\"monospace\" font-size=\"14\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"#111111\"/>\n",
+            self.grid.width() * CELL_WIDTH,
+            self.grid.height() * CELL_HEIGHT
+        );
+
+        self.cells()
+            .filter(|(_, _, c, _)| *c != '.')
+            .for_each(|(row, col, c, mark)| {
+                out.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>\n",
+                    col * CELL_WIDTH,
+                    (row + 1) * CELL_HEIGHT - 4,
+                    mark.colour(),
+                    escape(c)
+                ));
+            });
+
+        out.push_str("</svg>\n");
+
+        return out;
+    }
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '<' => String::from("&lt;"),
+        '>' => String::from("&gt;"),
+        '&' => String::from("&amp;"),
+        '"' => String::from("&quot;"),
+        c => c.to_string(),
+    }
+}
+
+#[allow(dead_code)]
+fn annotate_example(f: impl FnOnce(&Annotated)) {
+    use crate::{extract_gears, extract_numbers};
+
+    let text = common::day_file!("example");
+    let rules = Rules::default();
+    let grid = Grid::parse(&text, &rules.symbols).unwrap();
+    let parts = extract_numbers(&grid).unwrap();
+    let gears = extract_gears(&parts, &grid, &rules);
+
+    f(&Annotated::new(&grid, &parts, &gears, &rules));
+}
+
+#[test]
+fn test_marks() {
+    annotate_example(|annotated| {
+        // 467 touches the gear below it
+        assert_eq!(annotated.mark(0, 0), Mark::ValidPart);
+        assert_eq!(annotated.mark(0, 2), Mark::ValidPart);
+        assert_eq!(annotated.mark(0, 3), Mark::Plain);
+        assert_eq!(annotated.mark(1, 3), Mark::Gear);
+
+        // 114 and 58 don't touch anything
+        assert_eq!(annotated.mark(0, 5), Mark::OrphanPart);
+        assert_eq!(annotated.mark(5, 7), Mark::OrphanPart);
+
+        // Only one part touches the star next to 617
+        assert_eq!(annotated.mark(4, 3), Mark::Symbol);
+        assert_eq!(annotated.mark(3, 6), Mark::Symbol);
+    });
+}
+
+#[test]
+fn test_ansi() {
+    annotate_example(|annotated| {
+        let ansi = annotated.ansi();
+        let first_line = ansi.lines().next().unwrap();
+
+        assert_eq!(ansi.lines().count(), 10);
+        assert_eq!(
+            first_line,
+            "\x1b[1;32m4\x1b[0m\x1b[1;32m6\x1b[0m\x1b[1;32m7\x1b[0m..\
+             \x1b[1;31m1\x1b[0m\x1b[1;31m1\x1b[0m\x1b[1;31m4\x1b[0m.."
+        );
+    });
+}
+
+#[test]
+fn test_html_and_svg() {
+    annotate_example(|annotated| {
+        let html = annotated.html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<span class=\"gear\">*</span>"));
+        assert!(html.contains("<span class=\"orphan\">1</span>"));
+
+        let svg = annotated.svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("fill=\"#d9a400\">*</text>"));
+    });
+
+    assert_eq!(escape('&'), "&amp;");
+    assert_eq!(escape('<'), "&lt;");
+}
+
+#[test]
+fn test_render_multibyte() {
+    use crate::{extract_gears, extract_numbers};
+
+    let rules = Rules::default();
+    let grid = Grid::parse("★1\n.§", &rules.symbols).unwrap();
+    let parts = extract_numbers(&grid).unwrap();
+    let gears = extract_gears(&parts, &grid, &rules);
+    let annotated = Annotated::new(&grid, &parts, &gears, &rules);
+
+    assert_eq!(annotated.mark(0, 0), Mark::Symbol);
+    assert_eq!(annotated.mark(0, 1), Mark::ValidPart);
+    assert_eq!(annotated.mark(1, 1), Mark::Symbol);
+
+    assert_eq!(
+        annotated.ansi(),
+        "\x1b[1;36m★\x1b[0m\x1b[1;32m1\x1b[0m\n.\x1b[1;36m§\x1b[0m\n"
+    );
+    assert!(annotated.html().contains("<span class=\"symbol\">§</span>"));
+}
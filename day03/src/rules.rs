@@ -0,0 +1,173 @@
+// Everything about the schematic format that isn't fixed by the grid itself: which cells count as
+// symbols, which cells count as touching a part number, and what makes a gear.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    pub connectivity: Connectivity,
+    pub symbols: Symbols,
+    pub gear: char,
+    pub gear_parts: PartCount,
+}
+
+impl Default for Rules {
+    // As defined by the puzzle
+    fn default() -> Self {
+        Self {
+            connectivity: Connectivity::Eight,
+            symbols: Symbols::Except(vec!['.']),
+            gear: '*',
+            gear_parts: PartCount::Exactly(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Connectivity {
+    // Only cells sharing an edge with a digit
+    Four,
+    // Diagonals count too
+    Eight,
+}
+
+// Digits are never symbols, whatever the set says.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symbols {
+    Only(Vec<char>),
+    Except(Vec<char>),
+}
+
+impl Symbols {
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_ascii_digit() {
+            return false;
+        }
+
+        match self {
+            Symbols::Only(set) => set.contains(&c),
+            Symbols::Except(set) => !set.contains(&c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PartCount {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl PartCount {
+    pub fn matches(&self, count: usize) -> bool {
+        match self {
+            PartCount::Exactly(n) => count == *n,
+            PartCount::AtLeast(n) => count >= *n,
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self, String> {
+        let invalid = |_| format!("gear parts must look like 2 or 2+, got {}", s);
+
+        match s.strip_suffix('+') {
+            Some(n) => Ok(PartCount::AtLeast(n.parse().map_err(invalid)?)),
+            None => Ok(PartCount::Exactly(s.parse().map_err(invalid)?)),
+        }
+    }
+}
+
+impl Rules {
+    // Pulls rule flags out of the argument list, returning the remaining arguments.
+    //
+    //   --adjacency 4|8
+    //   --symbols <chars>      only these characters are symbols
+    //   --not-symbols <chars>  everything except these characters is a symbol
+    //   --gear <char>
+    //   --gear-parts N|N+      exactly N, or at least N part numbers
+    pub fn from_args(args: &[String]) -> Result<(Self, Vec<String>), String> {
+        let mut rules = Self::default();
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {}", arg))
+            };
+
+            match arg.as_str() {
+                "--adjacency" => {
+                    rules.connectivity = match value()?.as_str() {
+                        "4" => Connectivity::Four,
+                        "8" => Connectivity::Eight,
+                        other => return Err(format!("adjacency must be 4 or 8, got {}", other)),
+                    }
+                }
+                "--symbols" => rules.symbols = Symbols::Only(value()?.chars().collect()),
+                "--not-symbols" => rules.symbols = Symbols::Except(value()?.chars().collect()),
+                "--gear" => {
+                    let value = value()?;
+                    let mut chars = value.chars();
+                    rules.gear = match (chars.next(), chars.next()) {
+                        (Some(c), None) => c,
+                        _ => return Err(format!("gear must be a single character, got {}", value)),
+                    }
+                }
+                "--gear-parts" => rules.gear_parts = PartCount::parse(value()?)?,
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        return Ok((rules, rest));
+    }
+}
+
+#[allow(dead_code)]
+fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn test_symbols() {
+    let default = Rules::default().symbols;
+    assert!(default.contains('*'));
+    assert!(default.contains('#'));
+    assert!(!default.contains('.'));
+    assert!(!default.contains('7'));
+
+    let only = Symbols::Only(vec!['#', '7']);
+    assert!(only.contains('#'));
+    assert!(!only.contains('*'));
+    assert!(!only.contains('7'));
+}
+
+#[test]
+fn test_part_count() {
+    assert!(PartCount::Exactly(2).matches(2));
+    assert!(!PartCount::Exactly(2).matches(3));
+    assert!(PartCount::AtLeast(2).matches(3));
+    assert!(!PartCount::AtLeast(2).matches(1));
+}
+
+#[test]
+fn test_from_args() {
+    let (rules, rest) = Rules::from_args(&args("example")).unwrap();
+    assert_eq!(rules, Rules::default());
+    assert_eq!(rest, args("example"));
+
+    let (rules, rest) = Rules::from_args(&args(
+        "--adjacency 4 --symbols #$ --gear $ --gear-parts 3+ a b",
+    ))
+    .unwrap();
+    assert_eq!(
+        rules,
+        Rules {
+            connectivity: Connectivity::Four,
+            symbols: Symbols::Only(vec!['#', '$']),
+            gear: '$',
+            gear_parts: PartCount::AtLeast(3),
+        }
+    );
+    assert_eq!(rest, args("a b"));
+
+    assert!(Rules::from_args(&args("--adjacency 6")).is_err());
+    assert!(Rules::from_args(&args("--gear **")).is_err());
+    assert!(Rules::from_args(&args("--gear-parts two")).is_err());
+    assert!(Rules::from_args(&args("--symbols")).is_err());
+}
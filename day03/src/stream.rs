@@ -0,0 +1,366 @@
+// Streaming analysis for schematics too large to hold in memory. Rows are read one at a time and
+// only a three row window is kept: once the row below a part number has been read its
+// neighbourhood is complete, and once the row below that has been read no further part can reach
+// a gear above it. Everything is emitted as soon as it is known.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use common::{Num, Overflow};
+
+use crate::grid::{Grid, GridError};
+use crate::rules::Rules;
+use crate::{extract_gears, gear_ratio, is_valid, row_numbers, PartNo};
+
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    // A part number touching a symbol, with its position in the whole schematic
+    Part(PartNo),
+    // A gear with the right number of parts, at its position in the whole schematic
+    Gear {
+        row: usize,
+        col: usize,
+        parts: Vec<usize>,
+    },
+}
+
+// Running answers to both parts, built up from the events as they arrive.
+pub struct Totals<N> {
+    pub part_sum: Result<N, Overflow>,
+    pub ratio_sum: Result<N, Overflow>,
+}
+
+impl<N: Num> Totals<N> {
+    pub fn new() -> Self {
+        Self {
+            part_sum: Ok(N::zero()),
+            ratio_sum: Ok(N::zero()),
+        }
+    }
+
+    pub fn add(&mut self, event: &Event) {
+        match event {
+            Event::Part(part_no) => {
+                self.part_sum = self
+                    .part_sum
+                    .as_ref()
+                    .map_err(Overflow::clone)
+                    .and_then(|sum| {
+                        sum.add(&N::from_u64(part_no.digits as u64), "the part number sum")
+                    })
+            }
+            Event::Gear { parts, .. } => {
+                self.ratio_sum = self
+                    .ratio_sum
+                    .as_ref()
+                    .map_err(Overflow::clone)
+                    .and_then(|sum| sum.add(&gear_ratio(parts)?, "the gear ratio sum"))
+            }
+        }
+    }
+}
+
+// Lines are read the way `Grid::parse` reads the whole text: blank lines and whitespace before the
+// first row or after the last one are ignored, but anywhere else they are rows of the grid.
+pub fn scan(
+    reader: impl BufRead,
+    rules: &Rules,
+    mut emit: impl FnMut(Event),
+) -> Result<(), GridError> {
+    let mut window: Vec<String> = Vec::with_capacity(3);
+    // Gears touched so far which may still pick up more parts, keyed by position
+    let mut pending: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+    let mut width = None;
+    let mut row_no = 0;
+
+    // Adds a row to the grid and processes the one before it, which now has everything around it
+    let mut push_row = |line: &str, row_no: &mut usize| {
+        let found = line.chars().count();
+        match width {
+            None => width = Some(found),
+            Some(expected) if expected != found => {
+                return Err(GridError::Ragged {
+                    row: *row_no,
+                    expected,
+                    found,
+                })
+            }
+            _ => (),
+        }
+
+        if window.len() == 3 {
+            window.remove(0);
+        }
+        window.push(line.to_string());
+
+        if *row_no > 0 {
+            process_row(&window, *row_no - 1, rules, &mut pending, &mut emit)?;
+        }
+        *row_no += 1;
+
+        return Ok(());
+    };
+
+    // The last line seen with anything on it, and the blank lines since, which are only rows if
+    // there is another line after them
+    let mut last: Option<String> = None;
+    let mut blank: Vec<String> = Vec::new();
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| GridError::Unreadable {
+            row: row_no + last.iter().count() + blank.len(),
+            message: e.to_string(),
+        })?;
+        if line.trim().is_empty() {
+            if last.is_some() {
+                blank.push(line);
+            }
+            continue;
+        }
+
+        match last.replace(line) {
+            None => (),
+            Some(previous) => {
+                push_row(&previous, &mut row_no)?;
+                blank
+                    .drain(..)
+                    .try_for_each(|line| push_row(&line, &mut row_no))?;
+            }
+        }
+
+        // Leading whitespace only belongs to the first row
+        if row_no == 0 {
+            last = last.map(|line| line.trim_start().to_string());
+        }
+    }
+
+    // ...and trailing whitespace only to the last
+    match last {
+        None => return Err(GridError::Empty),
+        Some(line) => push_row(line.trim_end(), &mut row_no)?,
+    }
+
+    // The last row never gets a row below it
+    if window.len() == 3 {
+        window.remove(0);
+    }
+    process_row(&window, row_no - 1, rules, &mut pending, &mut emit)?;
+    flush_gears(&mut pending, row_no, rules, &mut emit);
+
+    return Ok(());
+}
+
+// Finds the parts on `row_no`, which is the second row of the window unless it's the first row of
+// the schematic.
+fn process_row(
+    window: &[String],
+    row_no: usize,
+    rules: &Rules,
+    pending: &mut BTreeMap<(usize, usize), Vec<usize>>,
+    emit: &mut impl FnMut(Event),
+) -> Result<(), GridError> {
+    let window_row = if row_no == 0 { 0 } else { 1 };
+    let first_row = row_no - window_row;
+
+    let rows = window.iter().map(String::as_str).collect::<Vec<_>>();
+    let grid = Grid::from_rows(&rows, &rules.symbols)?;
+
+    // Only this row's numbers, read on this thread since each window is tiny. The window's rows
+    // are counted from its first row.
+    let parts = row_numbers(&grid, window_row).map_err(|e| match e {
+        GridError::TooLarge { row, col } => GridError::TooLarge {
+            row: row + first_row,
+            col,
+        },
+        e => e,
+    })?;
+
+    extract_gears(&parts, &grid, rules)
+        .into_iter()
+        .for_each(|((row, col), digits)| {
+            pending
+                .entry((row + first_row, col))
+                .or_default()
+                .extend(digits);
+        });
+
+    parts
+        .into_iter()
+        .filter(|part_no| is_valid(part_no, &grid, rules))
+        .for_each(|part_no| {
+            emit(Event::Part(PartNo {
+                row: row_no,
+                ..part_no
+            }))
+        });
+
+    // Parts further down can't reach gears above this row any more
+    flush_gears(pending, row_no, rules, emit);
+
+    return Ok(());
+}
+
+// Emits and forgets every pending gear above `before_row`
+fn flush_gears(
+    pending: &mut BTreeMap<(usize, usize), Vec<usize>>,
+    before_row: usize,
+    rules: &Rules,
+    emit: &mut impl FnMut(Event),
+) {
+    while let Some(entry) = pending.first_entry() {
+        let (row, col) = *entry.key();
+        if row >= before_row {
+            break;
+        }
+
+        let parts = entry.remove();
+        if rules.gear_parts.matches(parts.len()) {
+            emit(Event::Gear { row, col, parts });
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn sums(text: &str, rules: &Rules) -> (u64, u64) {
+    let mut totals = Totals::<u64>::new();
+    scan(text.as_bytes(), rules, |event| totals.add(&event)).unwrap();
+
+    (totals.part_sum.unwrap(), totals.ratio_sum.unwrap())
+}
+
+#[test]
+fn test_scan_matches_in_memory() {
+    use crate::rules::{Connectivity, PartCount};
+    use crate::{part_one, part_two};
+
+    let four = Rules {
+        connectivity: Connectivity::Four,
+        gear_parts: PartCount::AtLeast(1),
+        ..Rules::default()
+    };
+
+    for rules in [Rules::default(), four] {
+        for filename in ["example", "input"] {
+            let text = common::day_file!(filename);
+
+            assert_eq!(
+                sums(&text, &rules),
+                (
+                    part_one(&text, &rules).unwrap(),
+                    part_two(&text, &rules).unwrap()
+                )
+            );
+        }
+    }
+}
+
+#[test]
+fn test_scan_emits_in_order() {
+    let text = common::day_file!("example");
+    let mut events = Vec::new();
+    scan(text.as_bytes(), &Rules::default(), |e| events.push(e)).unwrap();
+
+    assert_eq!(
+        events[0],
+        Event::Part(PartNo {
+            digits: 467,
+            row: 0,
+            col: 0,
+            width: 3
+        })
+    );
+    assert_eq!(
+        events[3],
+        Event::Gear {
+            row: 1,
+            col: 3,
+            parts: vec![467, 35]
+        }
+    );
+    assert_eq!(
+        events.last().unwrap(),
+        &Event::Gear {
+            row: 8,
+            col: 5,
+            parts: vec![755, 598]
+        }
+    );
+}
+
+#[test]
+fn test_scan_edge_cases() {
+    assert_eq!(sums("12*3", &Rules::default()), (15, 36));
+    assert_eq!(sums("1\n*\n2\n", &Rules::default()), (3, 2));
+
+    assert_eq!(
+        scan("12*3\n..\n".as_bytes(), &Rules::default(), |_| ()),
+        Err(GridError::Ragged {
+            row: 1,
+            expected: 4,
+            found: 2
+        })
+    );
+    assert_eq!(
+        scan("\n".as_bytes(), &Rules::default(), |_| ()),
+        Err(GridError::Empty)
+    );
+    assert!(matches!(
+        scan(&b"12*3\n\xff\xfe..\n"[..], &Rules::default(), |_| ()),
+        Err(GridError::Unreadable { row: 1, .. })
+    ));
+}
+
+#[test]
+fn test_scan_reads_lines_like_grid() {
+    use crate::{part_one, part_two};
+
+    let rules = Rules::default();
+    let texts = [
+        "\n\n467.\n..*3\n\n \n",
+        "  467.\n..*3  ",
+        "467.\n\n..*3",
+        "467.\n    \n..*3\n",
+        "467. \n..*3",
+        "\t\n",
+    ];
+
+    for text in texts {
+        let mut totals = Totals::<u64>::new();
+        let streamed = scan(text.as_bytes(), &rules, |event| totals.add(&event))
+            .map(|()| (totals.part_sum.unwrap(), totals.ratio_sum.unwrap()));
+        let in_memory = Grid::parse(text, &rules.symbols).map(|_| {
+            (
+                part_one(text, &rules).unwrap(),
+                part_two(text, &rules).unwrap(),
+            )
+        });
+
+        assert_eq!(streamed, in_memory, "{:?}", text);
+    }
+}
+
+#[test]
+fn test_scan_multibyte_symbols() {
+    let text = "12§.\n..★3\n4...\n";
+    assert_eq!(sums(text, &Rules::default()), (15, 0));
+
+    let rules = Rules {
+        gear: '★',
+        ..Rules::default()
+    };
+    assert_eq!(sums(text, &rules), (15, 36));
+}
+
+#[test]
+fn test_totals_overflow() {
+    let mut totals = Totals::<u64>::new();
+    scan(
+        "9999999999*9999999999".as_bytes(),
+        &Rules::default(),
+        |event| totals.add(&event),
+    )
+    .unwrap();
+
+    assert_eq!(totals.part_sum, Ok(19999999998));
+    assert!(totals.ratio_sum.is_err());
+}
@@ -0,0 +1,50 @@
+// Scratchcards, laid out like the puzzle input with numbers right aligned in two columns.
+
+use common::gen::{Param, Rng, Sizes};
+
+const LARGEST_NUMBER: u64 = 99;
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "cards",
+        default: 200,
+        help: "number of cards",
+    },
+    Param {
+        name: "winners",
+        default: 10,
+        help: "winning numbers per card",
+    },
+    Param {
+        name: "values",
+        default: 25,
+        help: "numbers you have per card",
+    },
+];
+
+pub fn generate(rng: &mut Rng, sizes: &Sizes) -> String {
+    let cards = sizes.get("cards");
+    let id_width = cards.to_string().len();
+    let winners = sizes.get("winners").min(LARGEST_NUMBER) as usize;
+    let values = sizes.get("values").min(LARGEST_NUMBER) as usize;
+
+    let numbers = |numbers: Vec<u64>| {
+        numbers
+            .iter()
+            .map(|n| format!("{:>2}", n))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    (1..=cards)
+        .map(|id| {
+            format!(
+                "Card {:>width$}: {} | {}\n",
+                id,
+                numbers(rng.sample(1, LARGEST_NUMBER, winners)),
+                numbers(rng.sample(1, LARGEST_NUMBER, values)),
+                width = id_width
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,153 @@
+#![allow(clippy::needless_return)]
+
+use common::bench::Bencher;
+use common::parse::{number, parse_lines};
+use common::{checked_sum, Error, Num, Overflow, ParseError};
+
+pub mod gen;
+
+struct Card {
+    winners: Vec<usize>,
+    values: Vec<usize>,
+}
+
+impl Card {
+    fn parse(line: &str) -> Result<Self, String> {
+        let (winners, values) = line
+            .split(": ")
+            .nth(1)
+            .and_then(|numbers| numbers.split_once(" | "))
+            .ok_or("expected \"Card <id>: <winners> | <values>\"")?;
+
+        let winners = winners
+            .split(' ')
+            .filter(|v| !v.is_empty())
+            .map(number)
+            .collect::<Result<_, _>>()?;
+
+        let values = values
+            .split(' ')
+            .filter(|v| !v.is_empty())
+            .map(number)
+            .collect::<Result<_, _>>()?;
+
+        return Ok(Self { winners, values });
+    }
+
+    fn score<N: Num>(&self) -> Result<N, Overflow> {
+        let winning_values: Vec<_> = self
+            .values
+            .iter()
+            .filter(|v| self.winners.contains(v))
+            .collect();
+
+        return match winning_values.len() {
+            0 => Ok(N::zero()),
+            v => {
+                let r = v - 1;
+                N::pow2(r.try_into().unwrap(), "a card score")
+            }
+        };
+    }
+}
+
+pub fn part_one<N: Num>(text: &str) -> Result<N, Error> {
+    return Ok(score_sum(&parse(text)?)?);
+}
+
+// Times parsing and solving separately, for `aoc bench`
+pub fn bench(text: &str, b: &mut Bencher) -> Result<(), Error> {
+    let cards = b.run("parse", || parse(text))?;
+    b.run("part one", || score_sum::<u64>(&cards))?;
+
+    return Ok(());
+}
+
+fn parse(text: &str) -> Result<Vec<Card>, ParseError> {
+    parse_lines(text, Card::parse)
+}
+
+fn score_sum<N: Num>(cards: &[Card]) -> Result<N, Overflow> {
+    let scores = cards
+        .iter()
+        .map(Card::score::<N>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    return checked_sum(scores, "the card score sum");
+}
+
+#[test]
+fn test_part_one() {
+    use common::{day_file, BigUint};
+
+    assert_eq!(part_one::<u64>(&day_file!("example")), Ok(13));
+    assert_eq!(part_one::<u64>(&day_file!("input")), Ok(23028));
+    assert_eq!(
+        part_one::<BigUint>(&day_file!("input")),
+        Ok(BigUint::from(23028))
+    );
+}
+
+#[test]
+fn test_invalid_input() {
+    let error = |line, message: &str| Err(ParseError::new(line, message).into());
+
+    assert_eq!(part_one::<u64>(""), Ok(0));
+    assert_eq!(
+        part_one::<u64>("Card 1: 1 2 | 2 3\nCard 2: 1 2 3"),
+        error(2, "expected \"Card <id>: <winners> | <values>\"")
+    );
+    assert_eq!(
+        part_one::<u64>("Card 1: 1 -2 | 2 3"),
+        error(1, "expected a number, found \"-2\"")
+    );
+}
+
+#[test]
+fn test_score_overflow() {
+    use common::BigUint;
+
+    let winners = (1..=70)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let card = Card::parse(&format!("Card 1: {} | {}", winners, winners)).unwrap();
+
+    assert!(card.score::<u64>().is_err());
+    assert_eq!(card.score::<BigUint>(), BigUint::pow2(69, "test"));
+    assert_eq!(
+        card.score::<BigUint>().unwrap().to_string(),
+        "590295810358705651712"
+    );
+}
+
+#[test]
+fn test_generated_input() {
+    use crate::gen::{generate, PARAMS};
+    use common::gen::{Rng, Sizes};
+
+    let text = generate(&mut Rng::new(1), &Sizes::defaults(PARAMS));
+    let numbers = |numbers: &[usize]| {
+        numbers
+            .iter()
+            .map(|n| format!("{:>2}", n))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    text.lines().enumerate().for_each(|(i, line)| {
+        let card = Card::parse(line).unwrap();
+
+        assert_eq!(card.winners.len(), 10);
+        assert_eq!(card.values.len(), 25);
+        assert_eq!(
+            format!(
+                "Card {:>3}: {} | {}",
+                i + 1,
+                numbers(&card.winners),
+                numbers(&card.values)
+            ),
+            line
+        );
+    });
+}
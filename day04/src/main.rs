@@ -1,56 +1,30 @@
-fn main() {
-    println!("Part one example: {}", part_one("example"));
-    println!("Part one example: {}", part_one("input"));
-}
-
-struct Card {
-    winners: Vec<usize>,
-    values: Vec<usize>,
-}
-
-impl Card {
-    fn parse(line: &str) -> Self {
-        let mut numbers = line.split(": ").skip(1).next().unwrap().split(" | ");
+#![allow(clippy::needless_return)]
 
-        let (winners, values) = (numbers.next().unwrap(), numbers.next().unwrap());
+use common::input::command_line_inputs;
+use common::{answer, day_file, BigUint, Num};
+use day04::part_one;
 
-        let winners = winners
-            .trim()
-            .split(" ")
-            .filter(|v| !v.is_empty())
-            .map(|v| v.parse().unwrap())
-            .collect();
-
-        let values = values
-            .trim()
-            .split(" ")
-            .filter(|v| !v.is_empty())
-            .map(|v| v.parse().unwrap())
-            .collect();
+// day04 [--big] [filename|-|--text input ...]
+fn main() {
+    let (big, inputs) = command_line_inputs();
 
-        return Self { winners, values };
+    match big {
+        true => run::<BigUint>(&inputs),
+        false => run::<u64>(&inputs),
     }
+}
 
-    fn score(&self) -> usize {
-        let winning_values: Vec<_> = self
-            .values
-            .iter()
-            .filter(|v| self.winners.contains(v))
-            .collect();
-
-        return match winning_values.len() {
-            0 => 0,
-            v => {
-                let r = v - 1;
-                2usize.pow(r.try_into().unwrap())
-            }
-        };
+fn run<N: Num>(inputs: &[(String, String)]) {
+    if !inputs.is_empty() {
+        inputs.iter().for_each(|(label, text)| {
+            println!("Part one {}: {}", label, answer(part_one::<N>(text)));
+        });
+        return;
     }
-}
 
-fn part_one(filename: &str) -> usize {
-    let text = std::fs::read_to_string(filename).unwrap();
-    let cards = text.trim().lines().map(Card::parse).collect::<Vec<_>>();
+    let example = day_file!("example");
+    let input = day_file!("input");
 
-    return cards.iter().map(Card::score).sum();
+    println!("Part one example: {}", answer(part_one::<N>(&example)));
+    println!("Part one input: {}", answer(part_one::<N>(&input)));
 }
@@ -0,0 +1,91 @@
+// Almanacs: a list of seeds followed by a chain of maps, each converting one category into the
+// next. Source ranges within a map never overlap, as in the puzzle.
+
+use common::gen::{Param, Rng, Sizes};
+
+const CATEGORIES: &[&str] = &[
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "seeds",
+        default: 10,
+        help: "number of seed ranges, each given as a start and a length",
+    },
+    Param {
+        name: "maps",
+        default: 7,
+        help: "number of maps in the chain",
+    },
+    Param {
+        name: "ranges",
+        default: 30,
+        help: "range conversions per map",
+    },
+    Param {
+        name: "max",
+        default: 1 << 32,
+        help: "values are all below this",
+    },
+];
+
+// The puzzle's own categories come first, after that they're numbered.
+fn category(i: usize) -> String {
+    match i {
+        0 => String::from("seed"),
+        _ => match CATEGORIES.get(i - 1) {
+            Some(name) => name.to_string(),
+            None => format!("category{}", i),
+        },
+    }
+}
+
+pub fn generate(rng: &mut Rng, sizes: &Sizes) -> String {
+    let max = sizes.get("max").max(2);
+
+    let seeds = (0..sizes.get("seeds"))
+        .flat_map(|_| {
+            let start = rng.range(0, max - 1);
+            let length = rng.range(1, max - start);
+            [start, length]
+        })
+        .map(|v| format!(" {}", v))
+        .collect::<String>();
+
+    let mut text = format!("seeds:{}\n", seeds);
+
+    // The seeds line is only terminated by the blank line before the first map, so there's always
+    // at least one
+    for i in 0..sizes.get("maps").max(1) as usize {
+        text += &format!("\n{}-to-{} map:\n", category(i), category(i + 1));
+
+        // Distinct cut points, paired up, give non-overlapping source ranges
+        let mut cuts = (0..sizes.get("ranges") * 2)
+            .map(|_| rng.range(0, max))
+            .collect::<Vec<_>>();
+        cuts.sort();
+        cuts.dedup();
+
+        let mut ranges = cuts
+            .chunks_exact(2)
+            .map(|pair| {
+                let (source, length) = (pair[0], pair[1] - pair[0]);
+                (rng.range(0, max - length), source, length)
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut ranges);
+
+        ranges.iter().for_each(|(dest, source, length)| {
+            text += &format!("{} {} {}\n", dest, source, length);
+        });
+    }
+
+    return text;
+}
@@ -0,0 +1,447 @@
+#![allow(clippy::needless_return)]
+
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+use common::bench::Bencher;
+use common::parse::number;
+use common::{par, Error, Num, Overflow, ParseError};
+
+pub mod gen;
+
+pub fn part_one<N: Num>(text: &str) -> Result<N, Error> {
+    let (seeds, maps) = parse::<N>(text)?;
+
+    return Ok(lowest_location(&seeds, &maps)?);
+}
+
+pub fn part_two<N: Num>(text: &str) -> Result<N, Error> {
+    let (seeds, maps) = parse::<N>(text)?;
+    if seeds.len() % 2 != 0 {
+        return Err(ParseError::new(1, "seeds come in pairs of a start and a length").into());
+    }
+
+    return Ok(lowest_range_location(seeds, &maps)?);
+}
+
+// Times parsing and solving separately, for `aoc bench`
+pub fn bench(text: &str, b: &mut Bencher) -> Result<(), Error> {
+    let (seeds, maps) = b.run("parse", || parse::<u64>(text))?;
+    b.run("part one", || lowest_location(&seeds, &maps))?;
+    b.run_with_setup(
+        "part two",
+        || seeds.clone(),
+        |seeds| lowest_range_location(seeds, &maps),
+    )?;
+
+    return Ok(());
+}
+
+fn lowest_location<N: Num>(
+    seeds: &[N],
+    maps: &HashMap<String, CategoryMap<N>>,
+) -> Result<N, Overflow> {
+    let locations = seeds
+        .iter()
+        .map(|s| {
+            let trace = trace_down(
+                Trace {
+                    key: String::from("seed"),
+                    value: s.clone(),
+                    bound_dist: None,
+                },
+                maps,
+            )?;
+
+            return Ok(trace.value);
+        })
+        .collect::<Result<Vec<_>, Overflow>>()?;
+
+    return Ok(locations.into_iter().min().unwrap());
+}
+
+fn lowest_range_location<N: Num>(
+    seeds: Vec<N>,
+    maps: &HashMap<String, CategoryMap<N>>,
+) -> Result<N, Overflow> {
+    let seeds = get_seed_ranges(seeds)?;
+
+    // Each seed range is traced on its own thread
+    let mut traces = Vec::new();
+    for range in par::map(&seeds, |s| trace_range(s, maps)) {
+        traces.extend(range?);
+    }
+
+    let result = traces
+        .into_iter()
+        .min_by(|a, b| a.value.cmp(&b.value))
+        .unwrap()
+        .value;
+
+    return Ok(result);
+}
+
+fn trace_range<N: Num>(
+    range: &RangeInclusive<N>,
+    maps: &HashMap<String, CategoryMap<N>>,
+) -> Result<Vec<Trace<N>>, Overflow> {
+    let mut values = Vec::new();
+    let mut value = range.start().clone();
+
+    let trace = Trace {
+        key: String::from("seed"),
+        value: value.clone(),
+        bound_dist: None,
+    };
+
+    while range.contains(&value) {
+        let trace = trace_down(trace.clone(), maps)?;
+        let bound_dist = trace.bound_dist.clone();
+
+        values.push(trace);
+
+        // Without a boundary ahead, the rest of the range maps the same way
+        match bound_dist {
+            Some(bound_dist) => value = value.add(&bound_dist, "the next seed in a range")?,
+            None => break,
+        }
+    }
+
+    return Ok(values);
+}
+
+fn get_seed_ranges<N: Num>(seeds: Vec<N>) -> Result<Vec<RangeInclusive<N>>, Overflow> {
+    seeds
+        .windows(2)
+        .step_by(2)
+        .map(|slice| {
+            let start = slice[0].clone();
+            let end = start.add(&slice[1], "the end of a seed range")?;
+
+            return Ok(start..=end);
+        })
+        .collect()
+}
+
+// The maps are checked for loops when parsed, so this always reaches a category with no map
+fn trace_down<N: Num>(
+    mut trace: Trace<N>,
+    maps: &HashMap<String, CategoryMap<N>>,
+) -> Result<Trace<N>, Overflow> {
+    while let Some(m) = maps.get(&trace.key) {
+        trace = Trace {
+            key: m.output_key.clone(),
+            value: m.translate(&trace.value)?,
+            bound_dist: Trace::compare_bounds(trace.bound_dist, m.bound_dist(&trace.value)),
+        };
+    }
+
+    return Ok(trace);
+}
+
+#[derive(Debug, Clone)]
+struct Trace<N> {
+    key: String,
+    value: N,
+    bound_dist: Option<N>,
+}
+
+impl<N: Num> Trace<N> {
+    fn compare_bounds(a: Option<N>, b: Option<N>) -> Option<N> {
+        let bounds = vec![a, b];
+        let mut bounds = bounds.into_iter().flatten().collect::<Vec<N>>();
+        bounds.sort();
+
+        return bounds.first().cloned();
+    }
+}
+
+// Each category's map, keyed by the category it maps from
+type Maps<N> = HashMap<String, CategoryMap<N>>;
+
+#[derive(Debug)]
+struct CategoryMap<N> {
+    input_key: String,
+    output_key: String,
+    ranges: Vec<RangeConversion<N>>,
+    boundaries: Vec<N>,
+}
+
+impl<N: Num> CategoryMap<N> {
+    fn translate(&self, input: &N) -> Result<N, Overflow> {
+        for range in self.ranges.iter() {
+            if let Some(v) = range.translate(input)? {
+                return Ok(v);
+            }
+        }
+
+        return Ok(input.clone());
+    }
+
+    fn bound_dist(&self, input: &N) -> Option<N> {
+        let bound = self.boundaries.iter().find(|v| *v > input)?;
+
+        // Can't underflow, the bound is above the input
+        return bound.checked_sub(input);
+    }
+}
+
+#[derive(Debug)]
+struct RangeConversion<N> {
+    source: N,
+    dest: N,
+    length: N,
+}
+
+impl<N: Num> RangeConversion<N> {
+    fn translate(&self, input: &N) -> Result<Option<N>, Overflow> {
+        let distance = match input.checked_sub(&self.source) {
+            None => return Ok(None),
+            Some(distance) => distance,
+        };
+
+        if distance > self.length {
+            return Ok(None);
+        }
+
+        return self.dest.add(&distance, "a range conversion").map(Some);
+    }
+}
+
+// The seeds come first, on the first line, followed by each map: a header like "seed-to-soil map:"
+// and then its ranges
+fn parse<N: Num>(text: &str) -> Result<(Vec<N>, Maps<N>), ParseError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()));
+
+    let seeds: Vec<N> = lines
+        .next()
+        .and_then(|(_, line)| line.strip_prefix("seeds:"))
+        .ok_or_else(|| ParseError::new(1, "expected \"seeds: <seeds>\""))?
+        .split(' ')
+        .filter(|v| !v.is_empty())
+        .map(|v| number(v).map(N::from_u64))
+        .collect::<Result<_, _>>()
+        .map_err(|e| ParseError::new(1, &e))?;
+    if seeds.is_empty() {
+        return Err(ParseError::new(1, "no seeds"));
+    }
+
+    let mut lines = lines.filter(|(_, line)| !line.is_empty()).peekable();
+    let mut maps: Maps<N> = HashMap::new();
+    // Where each map starts, to point at if they loop
+    let mut headers: HashMap<String, usize> = HashMap::new();
+
+    while let Some((header_no, header)) = lines.next() {
+        let (input_key, output_key) =
+            parse_keys(header).map_err(|e| ParseError::new(header_no, &e))?;
+
+        let mut ranges = Vec::new();
+        while let Some((line_no, line)) =
+            lines.next_if(|(_, line)| line.starts_with(|c: char| c.is_ascii_digit()))
+        {
+            ranges.push(RangeConversion::parse(line).map_err(|e| ParseError::new(line_no, &e))?);
+        }
+
+        headers.insert(input_key.clone(), header_no);
+        maps.insert(
+            input_key.clone(),
+            CategoryMap::new(input_key, output_key, ranges),
+        );
+    }
+
+    // Seeds are traced from map to map until there isn't one, which never happens if they loop
+    let mut seen = HashSet::new();
+    let mut key = "seed";
+    while let Some(map) = maps.get(key) {
+        if !seen.insert(key) {
+            return Err(ParseError::new(
+                headers[key],
+                &format!("the maps loop back to {}", key),
+            ));
+        }
+        key = &map.output_key;
+    }
+
+    return Ok((seeds, maps));
+}
+
+// The input and output categories from a header like "seed-to-soil map:"
+fn parse_keys(header: &str) -> Result<(String, String), String> {
+    header
+        .split(' ')
+        .next()
+        .and_then(|keys| keys.split_once("-to-"))
+        .map(|(input, output)| (input.to_string(), output.to_string()))
+        .ok_or_else(|| {
+            format!(
+                "expected a map like \"seed-to-soil map:\", found {:?}",
+                header
+            )
+        })
+}
+
+impl<N: Num> CategoryMap<N> {
+    fn new(input_key: String, output_key: String, ranges: Vec<RangeConversion<N>>) -> Self {
+        // A range ending past the largest representable value has no upper boundary, since no
+        // value can reach it
+        let mut boundaries: Vec<_> = ranges
+            .iter()
+            .flat_map(|r| vec![Some(r.source.clone()), r.source.checked_add(&r.length)])
+            .flatten()
+            .collect();
+
+        boundaries.sort();
+
+        return Self {
+            input_key,
+            output_key,
+            ranges,
+            boundaries,
+        };
+    }
+}
+
+impl<N: Num> RangeConversion<N> {
+    fn parse(s: &str) -> Result<Self, String> {
+        let vals = s
+            .split(' ')
+            .filter(|v| !v.is_empty())
+            .map(number::<u64>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Ranges are given as 3 numbers.
+        // Example: 50 98 20
+        //                ^^ Length
+        //             ^^ Source range start
+        //          ^^ Destination range start
+        let [dest, source, length] = vals[..] else {
+            return Err(format!("expected 3 numbers, found {:?}", s));
+        };
+
+        return Ok(Self {
+            source: N::from_u64(source),
+            dest: N::from_u64(dest),
+            length: N::from_u64(length),
+        });
+    }
+}
+
+#[test]
+fn test_part_one() {
+    use common::day_file;
+
+    assert_eq!(part_one::<u64>(&day_file!("example")), Ok(35));
+    assert_eq!(part_one::<u64>(&day_file!("input")), Ok(324724204));
+}
+
+#[test]
+fn test_invalid_input() {
+    let error = |line, message: &str| Err(ParseError::new(line, message).into());
+
+    assert_eq!(part_one::<u64>(""), error(1, "expected \"seeds: <seeds>\""));
+    assert_eq!(part_one::<u64>("seeds:\n"), error(1, "no seeds"));
+    assert_eq!(
+        part_one::<u64>("seeds: 1 two"),
+        error(1, "expected a number, found \"two\"")
+    );
+    assert_eq!(
+        part_one::<u64>("seeds: 1\n\nseed-to-soil map:\n1 2\n"),
+        error(4, "expected 3 numbers, found \"1 2\"")
+    );
+    assert_eq!(
+        part_one::<u64>("seeds: 1\n\nseed to soil\n1 2 3\n"),
+        error(
+            3,
+            "expected a map like \"seed-to-soil map:\", found \"seed to soil\""
+        )
+    );
+    assert_eq!(
+        part_one::<u64>("seeds: 1\n\nseed-to-soil map:\n\nsoil-to-seed map:\n"),
+        error(3, "the maps loop back to seed")
+    );
+    assert_eq!(
+        part_two::<u64>("seeds: 1 2 3"),
+        error(1, "seeds come in pairs of a start and a length")
+    );
+
+    assert_eq!(part_one::<u64>("seeds: 7"), Ok(7));
+    assert_eq!(part_two::<u64>("seeds: 7 3"), Ok(7));
+}
+
+#[test]
+fn test_big_matches_checked() {
+    use common::{day_file, BigUint};
+
+    for filename in ["example", "input"] {
+        let text = day_file!(filename);
+
+        assert_eq!(
+            part_one::<BigUint>(&text).unwrap().to_string(),
+            part_one::<u64>(&text).unwrap().to_string()
+        );
+        assert_eq!(
+            part_two::<BigUint>(&text).unwrap().to_string(),
+            part_two::<u64>(&text).unwrap().to_string()
+        );
+    }
+}
+
+#[test]
+fn test_overflow() {
+    use common::BigUint;
+
+    let seeds = vec![u64::MAX - 1, 5];
+    assert!(get_seed_ranges(seeds).is_err());
+
+    let range = RangeConversion::<u64>::parse("18446744073709551615 0 10").unwrap();
+    assert!(range.translate(&5).is_err());
+    assert_eq!(range.translate(&0), Ok(Some(u64::MAX)));
+
+    let range = RangeConversion::<BigUint>::parse("18446744073709551615 0 10").unwrap();
+    assert_eq!(
+        range
+            .translate(&BigUint::from(5))
+            .unwrap()
+            .unwrap()
+            .to_string(),
+        "18446744073709551620"
+    );
+}
+
+#[allow(dead_code)]
+fn format_almanac(seeds: &[u64], maps: &HashMap<String, CategoryMap<u64>>) -> String {
+    let seeds = seeds.iter().map(|s| format!(" {}", s)).collect::<String>();
+    let mut text = format!("seeds:{}\n", seeds);
+
+    let mut key = "seed";
+    while let Some(map) = maps.get(key) {
+        text += &format!("\n{}-to-{} map:\n", map.input_key, map.output_key);
+        map.ranges.iter().for_each(|r| {
+            text += &format!("{} {} {}\n", r.dest, r.source, r.length);
+        });
+        key = &map.output_key;
+    }
+
+    return text;
+}
+
+#[test]
+fn test_generated_input() {
+    use crate::gen::{generate, PARAMS};
+    use common::gen::{Rng, Sizes};
+
+    for (seed, maps) in [(1, 7), (2, 10), (3, 1)] {
+        let sizes = Sizes::defaults(PARAMS).with("maps", maps);
+        let text = generate(&mut Rng::new(seed), &sizes);
+        let (seeds, almanac) = parse::<u64>(&text).unwrap();
+        let result = part_one::<u64>(&text);
+
+        assert_eq!(seeds.len(), 20);
+        assert_eq!(almanac.len(), maps as usize);
+        assert_eq!(format_almanac(&seeds, &almanac), text);
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,246 @@
+// External-memory scoring for hand files too big to hold in memory.
+//
+// Every hand is reduced to a compact sort key and its bid. Keys are sorted in fixed size runs
+// which get spilled to temporary files, then the runs are k-way merged back together in rank
+// order while the score is accumulated, so only one run (plus one record per run) is ever held in
+// memory. At most MAX_FAN_IN runs are open at once: when there are more, groups of them are first
+// merged into longer runs, as many times as it takes.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use common::{Error, Num, ParseError};
+
+use crate::{winnings, Hand, Rules};
+
+// Each record on disk is a little endian u32 key followed by a little endian u64 bid.
+const RECORD_SIZE: usize = 12;
+
+pub const DEFAULT_RUN_SIZE: usize = 4_000_000;
+
+// Well under the usual limit of 1024 open files
+const MAX_FAN_IN: usize = 64;
+
+type Record = (u32, u64);
+
+pub fn score_external<N: Num>(filename: &str, rules: &Rules, run_size: usize) -> Result<N, Error> {
+    let file = File::open(filename).map_err(io_error(filename))?;
+    let mut spill = SpillDir::create()?;
+    let mut runs: Vec<PathBuf> = Vec::new();
+    let mut run: Vec<Record> = Vec::with_capacity(run_size);
+
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| Error::Io(format!("{} line {}: {}", filename, i + 1, e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let hand = Hand::parse(&line, rules).map_err(|e| ParseError::new(i + 1, &e))?;
+        run.push((hand.sort_key(), hand.bid));
+
+        if run.len() >= run_size {
+            runs.push(spill.write_run(&mut run)?);
+        }
+    }
+
+    if !run.is_empty() {
+        runs.push(spill.write_run(&mut run)?);
+    }
+
+    // Merge groups of runs, keeping them in order so identical hands still rank as they were dealt
+    while runs.len() > MAX_FAN_IN {
+        runs = runs
+            .chunks(MAX_FAN_IN)
+            .map(|group| {
+                let (path, mut writer) = spill.new_run()?;
+                merge_runs(group, |record| {
+                    write_record(&mut writer, record).map_err(io_error(&path))
+                })?;
+                writer.flush().map_err(io_error(&path))?;
+
+                group.iter().for_each(|run| {
+                    let _ = std::fs::remove_file(run);
+                });
+                Ok(path)
+            })
+            .collect::<Result<_, Error>>()?;
+    }
+
+    let mut rank = 0;
+    let mut sum = N::zero();
+
+    merge_runs(&runs, |(_, bid)| {
+        rank += 1;
+        sum = sum.add(&winnings(bid, rank)?, "the total winnings")?;
+        Ok(())
+    })?;
+
+    return Ok(sum);
+}
+
+fn io_error(path: impl AsRef<Path>) -> impl Fn(std::io::Error) -> Error {
+    move |e| Error::Io(format!("{}: {}", path.as_ref().display(), e))
+}
+
+// Passes every record of the runs to `emit` in key order, or for equal keys in run order
+fn merge_runs(
+    runs: &[PathBuf],
+    mut emit: impl FnMut(Record) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut readers = runs
+        .iter()
+        .map(|path| File::open(path).map(BufReader::new).map_err(io_error(path)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut heap = BinaryHeap::new();
+    for (i, reader) in readers.iter_mut().enumerate() {
+        if let Some((key, bid)) = read_record(reader).map_err(io_error(&runs[i]))? {
+            heap.push(Reverse((key, i, bid)));
+        }
+    }
+
+    while let Some(Reverse((key, i, bid))) = heap.pop() {
+        emit((key, bid))?;
+
+        if let Some((key, bid)) = read_record(&mut readers[i]).map_err(io_error(&runs[i]))? {
+            heap.push(Reverse((key, i, bid)));
+        }
+    }
+
+    return Ok(());
+}
+
+// None once the run is used up
+fn read_record(reader: &mut impl Read) -> std::io::Result<Option<Record>> {
+    let mut buf = [0; RECORD_SIZE];
+    match reader.read_exact(&mut buf) {
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        result => result?,
+    }
+
+    let key = u32::from_le_bytes(buf[..4].try_into().unwrap());
+    let bid = u64::from_le_bytes(buf[4..].try_into().unwrap());
+
+    Ok(Some((key, bid)))
+}
+
+fn write_record(writer: &mut impl Write, (key, bid): Record) -> std::io::Result<()> {
+    writer.write_all(&key.to_le_bytes())?;
+    writer.write_all(&bid.to_le_bytes())
+}
+
+// Temporary directory holding the sorted runs, removed again when dropped.
+struct SpillDir {
+    path: PathBuf,
+    runs: usize,
+}
+
+impl SpillDir {
+    fn create() -> Result<Self, Error> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "day07-runs-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path).map_err(io_error(&path))?;
+
+        Ok(Self { path, runs: 0 })
+    }
+
+    // A new, empty run file
+    fn new_run(&mut self) -> Result<(PathBuf, BufWriter<File>), Error> {
+        let path = self.path.join(format!("run-{}", self.runs));
+        self.runs += 1;
+        let file = File::create(&path).map_err(io_error(&path))?;
+
+        return Ok((path, BufWriter::new(file)));
+    }
+
+    // Sorts the run, writes it out and leaves it empty for the next batch of hands.
+    fn write_run(&mut self, run: &mut Vec<Record>) -> Result<PathBuf, Error> {
+        // Stable, so identical hands keep the order they were dealt in, as they do in memory
+        run.sort_by_key(|(key, _)| *key);
+
+        let (path, mut writer) = self.new_run()?;
+        run.drain(..)
+            .try_for_each(|record| write_record(&mut writer, record))
+            .and_then(|()| writer.flush())
+            .map_err(io_error(&path))?;
+
+        return Ok(path);
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+#[test]
+fn test_score_external_matches_in_memory() {
+    use crate::{parse, score, JOKER_RULES, STD_RULES};
+    use common::day_file;
+
+    for rules in [STD_RULES, JOKER_RULES] {
+        for filename in ["example", "input"] {
+            let expected = Ok(score::<u64>(&parse(&day_file!(filename), &rules).unwrap()).unwrap());
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(filename);
+            let path = path.to_str().unwrap();
+
+            assert_eq!(score_external(path, &rules, 1), expected);
+            assert_eq!(score_external(path, &rules, 7), expected);
+            assert_eq!(score_external(path, &rules, DEFAULT_RUN_SIZE), expected);
+        }
+
+        // Identical hands rank in the order they were dealt, within a run and across runs
+        let text = (1..=500)
+            .map(|bid| format!("{} {}\n", ["KK677", "QQQJA", "T55J5"][bid % 3], bid))
+            .collect::<String>();
+        let path = std::env::temp_dir().join(format!("day07-duplicates-{}", std::process::id()));
+        std::fs::write(&path, &text).unwrap();
+        let expected = Ok(score::<u64>(&parse(&text, &rules).unwrap()).unwrap());
+
+        for run_size in [1, 7, 200, DEFAULT_RUN_SIZE] {
+            assert_eq!(
+                score_external(path.to_str().unwrap(), &rules, run_size),
+                expected
+            );
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn test_unreadable_input() {
+    let path = std::env::temp_dir().join(format!("day07-unreadable-{}", std::process::id()));
+    std::fs::write(&path, b"32T3K 765\n\xff\xfe\n").unwrap();
+
+    assert!(matches!(
+        score_external::<u64>(path.to_str().unwrap(), &crate::STD_RULES, 1),
+        Err(Error::Io(e)) if e.contains("line 2")
+    ));
+    assert!(matches!(
+        score_external::<u64>("/nonexistent/hands", &crate::STD_RULES, 1),
+        Err(Error::Io(_))
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_spill_dir_is_removed() {
+    let mut spill = SpillDir::create().unwrap();
+    let path = spill.path.clone();
+    spill.write_run(&mut vec![(1, 2)]).unwrap();
+    assert!(path.exists());
+
+    drop(spill);
+    assert!(!path.exists());
+}
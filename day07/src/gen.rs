@@ -0,0 +1,32 @@
+// Camel card tournaments: one hand of five cards and a bid per line.
+
+use common::gen::{Param, Rng, Sizes};
+
+const CARDS: &[char] = &[
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "hands",
+        default: 1000,
+        help: "number of hands",
+    },
+    Param {
+        name: "bid",
+        default: 1000,
+        help: "largest bid",
+    },
+];
+
+pub fn generate(rng: &mut Rng, sizes: &Sizes) -> String {
+    let max_bid = sizes.get("bid").max(1);
+
+    (0..sizes.get("hands"))
+        .map(|_| {
+            let cards = (0..5).map(|_| *rng.pick(CARDS)).collect::<String>();
+
+            format!("{} {}\n", cards, rng.range(1, max_bid))
+        })
+        .collect()
+}
@@ -0,0 +1,516 @@
+#![allow(clippy::needless_return)]
+
+use std::cmp::Ordering;
+use std::collections::hash_map::HashMap;
+
+use common::bench::Bencher;
+use common::parse::{number, parse_lines};
+use common::{checked_sum, par, Error, Num, Overflow, ParseError};
+
+pub mod external;
+pub mod gen;
+
+pub fn part_one<N: Num>(text: &str) -> Result<N, Error> {
+    let hands = parse(text, &STD_RULES)?;
+    return Ok(score(&hands)?);
+}
+
+pub fn part_two<N: Num>(text: &str) -> Result<N, Error> {
+    let hands = parse(text, &JOKER_RULES)?;
+    return Ok(score(&hands)?);
+}
+
+// Times parsing and solving separately, for `aoc bench`
+pub fn bench(text: &str, b: &mut Bencher) -> Result<(), Error> {
+    let hands = b.run("parse", || parse(text, &STD_RULES))?;
+    b.run("part one", || score::<u64>(&hands))?;
+
+    let hands = parse(text, &JOKER_RULES)?;
+    b.run("part two", || score::<u64>(&hands))?;
+
+    return Ok(());
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rules {
+    cards: [char; 13],
+    tests: [fn(&[char]) -> bool; 7],
+}
+
+pub const STD_RULES: Rules = Rules {
+    cards: [
+        '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+    ],
+    tests: [
+        (|_| true),
+        (|h| of_a_kind_std(h) == 2),
+        (|h| of_a_kind_std(h) == 2 && group_cards_std(h).len() == 3), // two pair
+        (|h| of_a_kind_std(h) == 3),
+        (|h| of_a_kind_std(h) == 3 && group_cards_std(h).len() == 2), // full  house
+        (|h| of_a_kind_std(h) == 4),
+        (|h| of_a_kind_std(h) == 5),
+    ],
+};
+
+fn group_cards_std(cards: &[char]) -> HashMap<char, u8> {
+    cards.iter().fold(HashMap::new(), |mut acc, c| {
+        *acc.entry(*c).or_insert(0) += 1;
+        acc
+    })
+}
+
+fn of_a_kind_std(cards: &[char]) -> u8 {
+    *group_cards_std(cards).values().max().unwrap()
+}
+
+pub const JOKER_RULES: Rules = Rules {
+    cards: [
+        'J', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'Q', 'K', 'A',
+    ],
+
+    tests: [
+        (|_| true),
+        (|h| of_a_kind_j(h) == 2),
+        (is_2pair_j),
+        (|h| of_a_kind_j(h) == 3),
+        (is_fullhouse_j),
+        (|h| of_a_kind_j(h) == 4),
+        (|h| of_a_kind_j(h) == 5),
+    ],
+};
+
+fn group_cards_j(cards: &[char]) -> (u8, HashMap<char, u8>) {
+    let mut groups = group_cards_std(cards);
+    let jokers = groups.remove_entry(&'J').unwrap_or(('J', 0)).1;
+
+    return (jokers, groups);
+}
+
+fn of_a_kind_j(cards: &[char]) -> u8 {
+    let (jokers, groups) = group_cards_j(cards);
+
+    groups.values().max().unwrap_or(&0) + jokers
+}
+
+fn is_2pair_j(chars: &[char]) -> bool {
+    let (jokers, groups) = group_cards_j(chars);
+
+    let pairs = groups.values().filter(|&v| v == &2).count();
+    jokers + pairs as u8 >= 2
+}
+
+fn is_fullhouse_j(chars: &[char]) -> bool {
+    let (jokers, groups) = group_cards_j(chars);
+    let groups = groups.values().collect::<Vec<_>>();
+
+    match jokers {
+        0 => groups.contains(&&2) && groups.contains(&&3),
+        _ => groups.len() <= 2,
+    }
+}
+
+fn parse(text: &str, rules: &Rules) -> Result<Vec<Hand>, ParseError> {
+    parse_lines(text, |l| Hand::parse(l, rules))
+}
+
+// Classifying a hand is the slow part, so each hand is classified once, across threads, rather than
+// on every comparison while sorting
+fn score<N: Num>(hands: &[Hand]) -> Result<N, Overflow> {
+    let mut ranked = par::map(hands, Hand::sort_key)
+        .into_iter()
+        .zip(hands)
+        .collect::<Vec<_>>();
+    // Stable, so identical hands rank in the order they were dealt
+    ranked.sort_by_key(|(key, _)| *key);
+
+    let winnings = ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (_, h))| winnings(h.bid, i as u64 + 1))
+        .collect::<Result<Vec<N>, _>>()?;
+
+    return checked_sum(winnings, "the total winnings");
+}
+
+fn winnings<N: Num>(bid: u64, rank: u64) -> Result<N, Overflow> {
+    N::from_u64(bid).mul(&N::from_u64(rank), "a hand's winnings")
+}
+
+#[derive(Debug, Clone)]
+struct Hand {
+    cards: Vec<char>,
+    bid: u64,
+    rules: Rules,
+}
+
+impl Hand {
+    fn base_rank(&self) -> u8 {
+        self.rules
+            .tests
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, f)| f(&self.cards))
+            .unwrap()
+            .0 as u8
+    }
+
+    // Packs the base rank and the rank of each card into a single integer which orders the same way
+    // as the hand itself: 3 bits of base rank followed by 4 bits per card.
+    fn sort_key(&self) -> u32 {
+        assert_eq!(self.cards.len(), 5, "hands must have exactly 5 cards");
+
+        self.cards
+            .iter()
+            .fold(self.base_rank() as u32, |key, card| {
+                key << 4 | self.rules.cards.iter().position(|c| c == card).unwrap() as u32
+            })
+    }
+
+    // Only hands of 5 cards the rules know about can be classified
+    fn parse(s: &str, rules: &Rules) -> Result<Self, String> {
+        let mut iter = s.split(' ');
+        let (cards, bid) = match (iter.next(), iter.next(), iter.next()) {
+            (Some(cards), Some(bid), None) => (cards.chars().collect::<Vec<_>>(), bid),
+            _ => return Err(String::from("expected \"<cards> <bid>\"")),
+        };
+
+        if cards.len() != 5 {
+            return Err(format!("expected 5 cards, found {}", cards.len()));
+        }
+        if let Some(card) = cards.iter().find(|c| !rules.cards.contains(c)) {
+            return Err(format!("unknown card {:?}", card));
+        }
+
+        Ok(Self {
+            rules: *rules,
+            cards,
+            bid: number(bid)?,
+        })
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn rank_card(card: &char, rules: &Rules) -> u8 {
+            rules.cards.iter().position(|&c| c == *card).unwrap() as u8
+        }
+
+        match self.base_rank().cmp(&other.base_rank()) {
+            Ordering::Equal => self
+                .cards
+                .iter()
+                .zip(other.cards.iter())
+                .find_map(|(a, b)| {
+                    if a != b {
+                        Some(rank_card(a, &self.rules).cmp(&rank_card(b, &self.rules)))
+                    } else {
+                        None
+                    }
+                })
+                .unwrap(),
+
+            result => result,
+        }
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for Hand {}
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+// Convenience method for testing
+#[allow(dead_code)]
+fn charvec(s: &str) -> Vec<char> {
+    s.chars().collect::<Vec<_>>()
+}
+
+// Reference implementation of the joker rules for testing: substitutes every non-joker card for
+// every joker and keeps the best category the standard rules give any of the resulting hands.
+#[cfg(test)]
+fn brute_force_rank_j(cards: &[char]) -> u8 {
+    let substitutes = &JOKER_RULES.cards[1..];
+    let jokers = cards
+        .iter()
+        .enumerate()
+        .filter(|(_, &c)| c == 'J')
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    (0..substitutes.len().pow(jokers.len() as u32))
+        .map(|mut n| {
+            let mut candidate = cards.to_vec();
+            jokers.iter().for_each(|&i| {
+                candidate[i] = substitutes[n % substitutes.len()];
+                n /= substitutes.len();
+            });
+
+            rank_with(&candidate, &STD_RULES)
+        })
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+fn rank_with(cards: &[char], rules: &Rules) -> u8 {
+    Hand {
+        cards: cards.to_vec(),
+        bid: 0,
+        rules: *rules,
+    }
+    .base_rank()
+}
+
+// Xorshift generator, so the randomized tests are reproducible without pulling in a crate.
+#[cfg(test)]
+struct TestRng(u64);
+
+#[cfg(test)]
+impl TestRng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn hand(&mut self, cards: &[char]) -> Vec<char> {
+        (0..5)
+            .map(|_| cards[self.next() as usize % cards.len()])
+            .collect()
+    }
+}
+
+// What follows is the evidence of my struggle to deal with a logic error where the example
+// problems and part one passed, but the actual test for part 2 did not pass.
+//
+// The problem was that I wan't handling a hand of all jokers correctly.
+
+#[test]
+fn test_part_one_example() {
+    assert_eq!(part_one::<u64>(&common::day_file!("example")), Ok(6440));
+}
+
+#[test]
+fn test_part_one_actual() {
+    assert_eq!(part_one::<u64>(&common::day_file!("input")), Ok(251121738));
+}
+
+#[test]
+fn test_part_two_example() {
+    assert_eq!(part_two::<u64>(&common::day_file!("example")), Ok(5905));
+}
+
+#[test]
+fn test_part_two_actual() {
+    use common::{day_file, BigUint};
+
+    assert_eq!(part_two::<u64>(&day_file!("input")), Ok(251421071));
+    assert_eq!(
+        part_two::<BigUint>(&day_file!("input")),
+        Ok(BigUint::from(251421071))
+    );
+}
+
+#[test]
+fn test_group_cards_j() {
+    assert_eq!(group_cards_j(&charvec("12345")).0, 0);
+    assert_eq!(group_cards_j(&charvec("J2345")).0, 1);
+    assert_eq!(group_cards_j(&charvec("JJ345")).0, 2);
+    assert_eq!(group_cards_j(&charvec("JJJ45")).0, 3);
+    assert_eq!(group_cards_j(&charvec("JJJJJ")).0, 5);
+
+    assert_eq!(group_cards_j(&charvec("22233")).1.get(&'2').unwrap(), &3);
+    assert_eq!(group_cards_j(&charvec("22233")).1.get(&'3').unwrap(), &2);
+    assert_eq!(
+        group_cards_j(&charvec("22233")).1.values().max().unwrap(),
+        &3
+    );
+    assert!(!group_cards_j(&charvec("JJJJJ")).1.contains_key(&'2'));
+}
+
+#[test]
+fn test_of_a_kind_j() {
+    assert_eq!(of_a_kind_j(&charvec("12345")), 1);
+    assert_eq!(of_a_kind_j(&charvec("22345")), 2);
+    assert_eq!(of_a_kind_j(&charvec("J2345")), 2);
+    assert_eq!(of_a_kind_j(&charvec("J2245")), 3);
+    assert_eq!(of_a_kind_j(&charvec("JJ245")), 3);
+    assert_eq!(of_a_kind_j(&charvec("JJJ45")), 4);
+    assert_eq!(of_a_kind_j(&charvec("JJ445")), 4);
+    assert_eq!(of_a_kind_j(&charvec("22222")), 5);
+    assert_eq!(of_a_kind_j(&charvec("JJ222")), 5);
+    assert_eq!(of_a_kind_j(&charvec("JJJJJ")), 5);
+}
+
+#[test]
+fn test_is_2pair_j() {
+    assert!(is_2pair_j(&charvec("22334")));
+    assert!(is_2pair_j(&charvec("J2334")));
+    assert!(is_2pair_j(&charvec("JJ345")));
+    assert!(is_2pair_j(&charvec("JJJ45")));
+
+    assert!(!is_2pair_j(&charvec("12345")));
+    assert!(!is_2pair_j(&charvec("22345")));
+    assert!(!is_2pair_j(&charvec("J2345")));
+}
+
+#[test]
+fn test_is_fullhouse_j() {
+    assert!(is_fullhouse_j(&charvec("22333")));
+    assert!(is_fullhouse_j(&charvec("J2333")));
+    assert!(is_fullhouse_j(&charvec("JJ333")));
+    assert!(is_fullhouse_j(&charvec("22J33")));
+    assert!(is_fullhouse_j(&charvec("J2J33")));
+    assert!(is_fullhouse_j(&charvec("JJJ34")));
+
+    assert!(!is_fullhouse_j(&charvec("12345")));
+    assert!(!is_fullhouse_j(&charvec("12222")));
+    assert!(!is_fullhouse_j(&charvec("22335")));
+    assert!(!is_fullhouse_j(&charvec("J2235")));
+    assert!(!is_fullhouse_j(&charvec("JJ245")));
+    assert!(!is_fullhouse_j(&charvec("JJ245")));
+}
+
+#[test]
+fn test_brute_force_rank_j() {
+    assert_eq!(brute_force_rank_j(&charvec("23456")), 0);
+    assert_eq!(brute_force_rank_j(&charvec("J2345")), 1);
+    assert_eq!(brute_force_rank_j(&charvec("J2234")), 3);
+    assert_eq!(brute_force_rank_j(&charvec("J2233")), 4);
+    assert_eq!(brute_force_rank_j(&charvec("JJJ23")), 5);
+    assert_eq!(brute_force_rank_j(&charvec("JJJJJ")), 6);
+}
+
+#[test]
+fn test_joker_rules_match_brute_force_random() {
+    let mut rng = TestRng(0x2023_0007);
+
+    (0..5_000).for_each(|_| {
+        let hand = rng.hand(&JOKER_RULES.cards);
+
+        assert_eq!(
+            rank_with(&hand, &JOKER_RULES),
+            brute_force_rank_j(&hand),
+            "hand: {}",
+            hand.iter().collect::<String>()
+        );
+    });
+}
+
+// Categories only depend on how the cards group, so five distinct non-jokers plus the joker are
+// enough to produce every shape a hand can take. Slow in debug builds, so run it with
+// `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn test_joker_rules_match_brute_force_exhaustive() {
+    let cards = charvec("J2345A");
+
+    (0..cards.len().pow(5)).for_each(|mut n| {
+        let hand = (0..5)
+            .map(|_| {
+                let card = cards[n % cards.len()];
+                n /= cards.len();
+                card
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rank_with(&hand, &JOKER_RULES),
+            brute_force_rank_j(&hand),
+            "hand: {}",
+            hand.iter().collect::<String>()
+        );
+    });
+}
+
+#[test]
+fn test_sort_key_orders_like_hands() {
+    let mut rng = TestRng(0x2023_0027);
+
+    for rules in [STD_RULES, JOKER_RULES] {
+        (0..2_000).for_each(|_| {
+            let [a, b] = [(); 2].map(|_| Hand {
+                cards: rng.hand(&rules.cards),
+                bid: 0,
+                rules,
+            });
+
+            assert_eq!(
+                a.sort_key().cmp(&b.sort_key()),
+                a.cmp(&b),
+                "{:?} vs {:?}",
+                a.cards,
+                b.cards
+            );
+        });
+    }
+}
+
+#[test]
+fn test_invalid_input() {
+    let error = |line, message: &str| Err(ParseError::new(line, message).into());
+
+    assert_eq!(part_one::<u64>(""), Ok(0));
+    assert_eq!(part_one::<u64>("32T3K 765\n32T3K 1"), Ok(767));
+    assert_eq!(
+        part_one::<u64>("32T3K 765\n\nKK677"),
+        error(3, "expected \"<cards> <bid>\"")
+    );
+    assert_eq!(
+        part_one::<u64>("32T3 765"),
+        error(1, "expected 5 cards, found 4")
+    );
+    assert_eq!(part_two::<u64>("32T3X 765"), error(1, "unknown card 'X'"));
+    assert_eq!(
+        part_two::<u64>("32T3K -765"),
+        error(1, "expected a number, found \"-765\"")
+    );
+}
+
+#[test]
+fn test_score_overflow() {
+    use common::BigUint;
+
+    let hands = vec![
+        Hand::parse(&format!("23456 {}", u64::MAX), &STD_RULES).unwrap(),
+        Hand::parse(&format!("AAAAA {}", u64::MAX), &STD_RULES).unwrap(),
+    ];
+
+    assert!(score::<u64>(&hands).is_err());
+    assert_eq!(
+        score::<BigUint>(&hands).unwrap().to_string(),
+        "55340232221128654845"
+    );
+}
+
+#[test]
+fn test_generated_input() {
+    use crate::gen::{generate, PARAMS};
+    use common::gen::{Rng, Sizes};
+
+    let text = generate(&mut Rng::new(1), &Sizes::defaults(PARAMS));
+
+    for rules in [STD_RULES, JOKER_RULES] {
+        let hands = text
+            .lines()
+            .map(|line| Hand::parse(line, &rules).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(hands.len(), 1000);
+        text.lines().zip(hands.iter()).for_each(|(line, hand)| {
+            let cards = hand.cards.iter().collect::<String>();
+            assert_eq!(format!("{} {}", cards, hand.bid), line);
+            hand.sort_key();
+        });
+    }
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+// The lines of calibration values
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = day01::part_one::<u64>(text);
+});
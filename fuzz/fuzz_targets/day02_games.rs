@@ -0,0 +1,9 @@
+#![no_main]
+
+// Game::parse, and Color::parse for each color in a round
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = day02::part_one::<u64>(text);
+    let _ = day02::part_two::<u64>(text);
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+// Grid::parse and extract_numbers, under the default rules and with 4-connectivity
+use day03::rules::{Connectivity, Rules};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let four = Rules {
+        connectivity: Connectivity::Four,
+        ..Rules::default()
+    };
+
+    for rules in [Rules::default(), four] {
+        let _ = day03::part_one::<u64>(text, &rules);
+        let _ = day03::part_two::<u64>(text, &rules);
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+// Card::parse
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = day04::part_one::<u64>(text);
+});
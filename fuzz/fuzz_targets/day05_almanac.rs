@@ -0,0 +1,10 @@
+#![no_main]
+
+// The seeds, the map headers (CategoryMap) and their ranges (RangeConversion). Only part one is run:
+// part two takes a step per boundary crossed across each seed range, so a few large numbers can
+// keep it busy for longer than the fuzzer's timeout.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = day05::part_one::<u64>(text);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+// Hand::parse, under the standard and joker rules
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = day07::part_one::<u64>(text);
+    let _ = day07::part_two::<u64>(text);
+});
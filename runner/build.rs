@@ -0,0 +1,34 @@
+#![allow(clippy::needless_return)]
+
+// Turns every entry in the answers registry into a test, so `cargo test` catches regressions the
+// same way `aoc verify` does.
+
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/answers.rs"]
+mod answers;
+
+fn main() {
+    let registry = Path::new("../answers.txt");
+    println!("cargo:rerun-if-changed={}", registry.display());
+
+    let answers = answers::Answers::load(registry).unwrap();
+    let tests = answers
+        .iter()
+        .map(|((day, part, input), answer)| {
+            let name = input
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>();
+
+            format!(
+                "#[test]\nfn answer_day{:02}_part{}_{}() {{\n    assert_recorded({}, {}, {:?}, {:?});\n}}\n",
+                day, part, name, day, part, input, answer
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("answer_tests.rs");
+    std::fs::write(out, tests.join("\n")).unwrap();
+}
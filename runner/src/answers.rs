@@ -0,0 +1,127 @@
+// The known answers registry. Answers are stored one per line as `<day> <part> <input> <answer>`,
+// where the input is a file name in the day's directory, and lines starting with # are comments.
+//
+// This file is also compiled into the build script, so it only depends on std.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+pub type Key = (u32, u8, String);
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<Key, String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || {
+                format!(
+                    "answers line {}: expected <day> <part> <input> <answer>, got {}",
+                    line_no + 1,
+                    line
+                )
+            };
+
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [day, part, input, answer] = fields[..] else {
+                return Err(invalid());
+            };
+
+            let day = day.parse().map_err(|_| invalid())?;
+            let part = part.parse().map_err(|_| invalid())?;
+            answers.record(day, part, input, answer);
+        }
+
+        return Ok(answers);
+    }
+
+    // A missing file is an empty registry
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_string()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, day: u32, part: u8, input: &str) -> Option<&str> {
+        self.entries
+            .get(&(day, part, input.to_string()))
+            .map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u32, part: u8, input: &str, answer: &str) {
+        self.entries
+            .insert((day, part, input.to_string()), answer.to_string());
+    }
+
+    // Only the build script walks the whole registry
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &String)> {
+        self.entries.iter()
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "# Known answers, checked by `aoc verify` and by the runner's tests."
+        )?;
+        writeln!(f, "# <day> <part> <input> <answer>")?;
+
+        self.entries
+            .iter()
+            .try_for_each(|((day, part, input), answer)| {
+                writeln!(f, "{} {} {} {}", day, part, input, answer)
+            })
+    }
+}
+
+#[test]
+fn test_parse() {
+    let answers = Answers::parse("# comment\n7 2 input 251421071\n\n1 1 example 142\n").unwrap();
+
+    assert_eq!(answers.get(7, 2, "input"), Some("251421071"));
+    assert_eq!(answers.get(1, 1, "example"), Some("142"));
+    assert_eq!(answers.get(1, 2, "example"), None);
+    assert_eq!(answers.iter().count(), 2);
+
+    assert!(Answers::parse("7 2 input").is_err());
+    assert!(Answers::parse("seven 2 input 1").is_err());
+    assert!(Answers::parse("7 2 input 1 2").is_err());
+}
+
+#[test]
+fn test_round_trip() {
+    let mut answers = Answers::default();
+    answers.record(7, 1, "input", "251121738");
+    answers.record(2, 2, "example", "2286");
+
+    let text = answers.to_string();
+    assert!(text.find("2 2 example").unwrap() < text.find("7 1 input").unwrap());
+    assert_eq!(Answers::parse(&text).unwrap(), answers);
+}
+
+#[test]
+fn test_registry_file_is_valid() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.txt");
+    let answers = Answers::load(&path).unwrap();
+
+    assert!(answers.get(7, 2, "input").is_some());
+    assert_eq!(answers.to_string(), std::fs::read_to_string(&path).unwrap());
+}
@@ -0,0 +1,182 @@
+// Benchmarks every day, timing parsing separately from each part.
+//
+//   aoc bench [day ...] [--input NAME] [--time MS] [--json FILE]
+//
+// Results are printed as a table, and with --json also written out with one result per line so
+// runs from different commits can be diffed. Timings are only meaningful with --release.
+//
+// Days are benchmarked one at a time, but parts which split their work across threads still do
+// so unless the runner is given --single-thread.
+
+use std::time::Duration;
+
+use common::bench::{format_duration, Bencher, Timing};
+use common::json;
+
+use crate::days::{self, Day, DAYS};
+
+const DEFAULT_BUDGET_MS: u64 = 500;
+
+#[derive(Debug)]
+pub struct DayTimings {
+    pub day: u32,
+    pub timings: Vec<Timing>,
+}
+
+pub fn run(day: &Day, input: &str, budget: Duration) -> Result<DayTimings, String> {
+    let text = day.read(input)?;
+
+    let mut b = Bencher::new(budget);
+    (day.bench)(&text, &mut b).map_err(|e| e.to_string())?;
+
+    return Ok(DayTimings {
+        day: day.number,
+        timings: b.timings().to_vec(),
+    });
+}
+
+pub fn bench(args: &[String]) -> Result<(), String> {
+    let mut input = String::from("input");
+    let mut budget = Duration::from_millis(DEFAULT_BUDGET_MS);
+    let mut json = None;
+    let mut selected = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--input" => input = value()?.clone(),
+            "--json" => json = Some(value()?.clone()),
+            "--time" => {
+                let value = value()?;
+                let ms = value.parse().map_err(|_| {
+                    format!("--time must be a number of milliseconds, got {}", value)
+                })?;
+                budget = Duration::from_millis(ms);
+            }
+            _ => selected.push(days::find(arg)?),
+        }
+    }
+
+    if selected.is_empty() {
+        selected = DAYS.iter().collect();
+    }
+
+    let results = selected
+        .iter()
+        .map(|day| run(day, &input, budget))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    print!("{}", table(&results));
+
+    if let Some(path) = json {
+        std::fs::write(&path, to_json(&input, &results)).map_err(|e| format!("{}: {}", path, e))?;
+    }
+
+    return Ok(());
+}
+
+fn table(results: &[DayTimings]) -> String {
+    let mut out = format!(
+        "{:>3}  {:<10} {:>7} {:>10} {:>10} {:>10}\n",
+        "day", "stage", "samples", "min", "median", "mean"
+    );
+
+    results.iter().for_each(|r| {
+        r.timings.iter().for_each(|t| {
+            out += &format!(
+                "{:>3}  {:<10} {:>7} {:>10} {:>10} {:>10}\n",
+                r.day,
+                t.stage,
+                t.samples,
+                format_duration(t.min),
+                format_duration(t.median),
+                format_duration(t.mean)
+            );
+        })
+    });
+
+    return out;
+}
+
+fn to_json(input: &str, results: &[DayTimings]) -> String {
+    let lines = results
+        .iter()
+        .flat_map(|r| {
+            r.timings.iter().map(move |t| {
+                format!(
+                    "    {{\"day\": {}, \"stage\": {}, \"samples\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}}}",
+                    r.day,
+                    json::string(&t.stage),
+                    t.samples,
+                    t.min.as_nanos(),
+                    t.median.as_nanos(),
+                    t.mean.as_nanos()
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "{{\n  \"input\": {},\n  \"results\": [\n{}\n  ]\n}}\n",
+        json::string(input),
+        lines.join(",\n")
+    )
+}
+
+#[test]
+fn test_bench_every_day() {
+    let results = DAYS
+        .iter()
+        .map(|day| run(day, "example", Duration::ZERO).unwrap())
+        .collect::<Vec<_>>();
+
+    results.iter().zip(DAYS).for_each(|(r, day)| {
+        let stages = r
+            .timings
+            .iter()
+            .map(|t| t.stage.as_str())
+            .collect::<Vec<_>>();
+        let parts = ["part one", "part two"];
+        assert_eq!(stages[0], "parse");
+        assert_eq!(stages[1..], parts[..day.parts.len()], "day {}", day.number);
+    });
+
+    let table = table(&results);
+    assert_eq!(
+        table.lines().count(),
+        1 + results.iter().map(|r| r.timings.len()).sum::<usize>()
+    );
+    assert!(table.lines().nth(1).unwrap().starts_with("  1  parse"));
+}
+
+#[test]
+fn test_to_json() {
+    let ns = Duration::from_nanos;
+    let results = [DayTimings {
+        day: 7,
+        timings: vec![Timing {
+            stage: String::from("part two"),
+            samples: 3,
+            min: ns(10),
+            median: ns(20),
+            mean: ns(30),
+        }],
+    }];
+
+    assert_eq!(
+        to_json("input", &results),
+        "{\n  \"input\": \"input\",\n  \"results\": [\n    {\"day\": 7, \"stage\": \"part two\", \"samples\": 3, \"min_ns\": 10, \"median_ns\": 20, \"mean_ns\": 30}\n  ]\n}\n"
+    );
+    // Debug would write \u{1}, which isn't JSON
+    assert!(to_json("a\"b\u{1}", &[]).contains("\"input\": \"a\\\"b\\u0001\","));
+}
+
+#[test]
+fn test_missing_input() {
+    assert!(run(days::find("7").unwrap(), "missing", Duration::ZERO).is_err());
+}
@@ -0,0 +1,193 @@
+// Authenticated, rate limited access to the puzzle server.
+//
+// Configuration comes from the environment:
+//
+//   AOC_SERVER    server to talk to, default https://adventofcode.com
+//   AOC_YEAR      puzzle year, default 2023
+//   AOC_SESSION   session cookie value, otherwise read from .session in the workspace root
+//   AOC_CACHE     cache directory, default .cache in the workspace root
+//   AOC_INTERVAL  minimum seconds between requests, default 5
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::days;
+use crate::http::{Request, Response};
+
+const USER_AGENT: &str = "aoc-runner/0.1 (personal puzzle runner, caches every input)";
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub server: String,
+    pub year: u32,
+    pub session: Option<String>,
+    pub cache: PathBuf,
+    pub min_interval: Duration,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let number = |name: &str, default: u64| match var(name) {
+            None => Ok(default),
+            Some(v) => v
+                .parse()
+                .map_err(|_| format!("{} must be a number, got {}", name, v)),
+        };
+
+        let session = var("AOC_SESSION").or_else(|| {
+            std::fs::read_to_string(days::root().join(".session"))
+                .ok()
+                .map(|s| s.trim().to_string())
+        });
+
+        return Ok(Self {
+            server: var("AOC_SERVER").unwrap_or_else(|| String::from("https://adventofcode.com")),
+            year: number("AOC_YEAR", 2023)? as u32,
+            session,
+            cache: var("AOC_CACHE")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| days::root().join(".cache")),
+            min_interval: Duration::from_secs(number("AOC_INTERVAL", 5)?),
+        });
+    }
+
+    // Where everything for one day is cached
+    pub fn day_cache(&self, day: u32) -> PathBuf {
+        self.cache
+            .join(self.year.to_string())
+            .join(format!("day{:02}", day))
+    }
+}
+
+pub struct Client {
+    pub config: Config,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    // The URL of a page of the current year's puzzles, e.g. "/day/7/input"
+    pub fn url(&self, path: &str) -> String {
+        format!(
+            "{}/{}{}",
+            self.config.server.trim_end_matches('/'),
+            self.config.year,
+            path
+        )
+    }
+
+    pub fn send(&self, request: Request) -> Result<Response, String> {
+        let session = self
+            .config
+            .session
+            .as_ref()
+            .ok_or("no session token: set AOC_SESSION or write it to .session")?;
+
+        self.wait_for_rate_limit()?;
+        let response = request
+            .header("Cookie", &format!("session={}", session))
+            .header("User-Agent", USER_AGENT)
+            .send();
+        self.stamp()?;
+
+        let response = response?;
+        if response.status == 429 {
+            return Err(String::from("rate limited by the server, try again later"));
+        }
+
+        return Ok(response);
+    }
+
+    // The time of the last request is kept in the cache so separate runs share the limit
+    fn stamp_path(&self) -> PathBuf {
+        self.config.cache.join(".last-request")
+    }
+
+    fn stamp(&self) -> Result<(), String> {
+        std::fs::create_dir_all(&self.config.cache).map_err(|e| e.to_string())?;
+        std::fs::write(self.stamp_path(), now_ms().to_string()).map_err(|e| e.to_string())
+    }
+
+    fn wait_for_rate_limit(&self) -> Result<(), String> {
+        let last = std::fs::read_to_string(self.stamp_path())
+            .ok()
+            .and_then(|s| s.trim().parse::<u128>().ok());
+
+        if let Some(last) = last {
+            let next = last + self.config.min_interval.as_millis();
+            let now = now_ms();
+
+            if next > now {
+                let wait = Duration::from_millis((next - now) as u64);
+                eprintln!("waiting {:.1}s before the next request", wait.as_secs_f64());
+                std::thread::sleep(wait);
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis()
+}
+
+// A client for a mock server, with its own empty cache directory
+#[cfg(test)]
+pub fn test_client(server: &str, name: &str, min_interval: Duration) -> Client {
+    let cache = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache);
+
+    Client::new(Config {
+        server: server.to_string(),
+        year: 2023,
+        session: Some(String::from("53551")),
+        cache,
+        min_interval,
+    })
+}
+
+#[test]
+fn test_requests_are_spaced_out() {
+    use crate::http::mock;
+
+    let server = mock::Server::start(vec![(200, "a"), (200, "b")]);
+    let client = test_client(&server.url, "spacing", Duration::from_millis(300));
+
+    let started = std::time::Instant::now();
+    client
+        .send(Request::get(&client.url("/day/1/input")))
+        .unwrap();
+    client
+        .send(Request::get(&client.url("/day/2/input")))
+        .unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(300));
+
+    let requests = server.requests();
+    assert!(requests[1].starts_with("GET /2023/day/2/input "));
+    assert!(requests[1].contains("Cookie: session=53551\r\n"));
+    std::fs::remove_dir_all(&client.config.cache).unwrap();
+}
+
+#[test]
+fn test_errors() {
+    use crate::http::mock;
+
+    let server = mock::Server::start(vec![(429, "")]);
+    let mut client = test_client(&server.url, "errors", Duration::ZERO);
+    assert!(client.send(Request::get(&client.url("/"))).is_err());
+    server.requests();
+
+    client.config.session = None;
+    assert!(client
+        .send(Request::get(&client.url("/")))
+        .unwrap_err()
+        .contains("session"));
+    std::fs::remove_dir_all(&client.config.cache).unwrap();
+}
@@ -0,0 +1,141 @@
+// Every day the runner knows about. New days get added here.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use common::bench::Bencher;
+use common::gen::{Param, Rng, Sizes};
+use common::Error;
+
+// Solves one part of a day's puzzle for the given input text
+pub type Part = fn(&str) -> Result<String, String>;
+
+pub struct Day {
+    pub number: u32,
+    // Directory of the day's crate and its input files, relative to the workspace root
+    pub dir: &'static str,
+    pub parts: &'static [Part],
+    // Times parsing and each part separately on the given input text
+    pub bench: fn(&str, &mut Bencher) -> Result<(), Error>,
+    pub gen_params: &'static [Param],
+    pub generate: fn(&mut Rng, &Sizes) -> String,
+}
+
+impl Day {
+    // The path of one of the day's input files, such as "example" or "input"
+    pub fn input(&self, name: &str) -> PathBuf {
+        root().join(self.dir).join(name)
+    }
+
+    pub fn read(&self, name: &str) -> Result<String, String> {
+        let path = self.input(name);
+
+        std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+pub fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn solved<N: Display>(result: Result<N, Error>) -> Result<String, String> {
+    result.map(|n| n.to_string()).map_err(|e| e.to_string())
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        dir: "day01",
+        parts: &[|text| solved(day01::part_one::<u64>(text))],
+        bench: day01::bench,
+        gen_params: day01::gen::PARAMS,
+        generate: day01::gen::generate,
+    },
+    Day {
+        number: 2,
+        dir: "day02",
+        parts: &[
+            |text| solved(day02::part_one::<u64>(text)),
+            |text| solved(day02::part_two::<u64>(text)),
+        ],
+        bench: day02::bench,
+        gen_params: day02::gen::PARAMS,
+        generate: day02::gen::generate,
+    },
+    Day {
+        number: 3,
+        dir: "day03",
+        parts: &[
+            |text| {
+                solved(day03::part_one::<u64>(
+                    text,
+                    &day03::rules::Rules::default(),
+                ))
+            },
+            |text| {
+                solved(day03::part_two::<u64>(
+                    text,
+                    &day03::rules::Rules::default(),
+                ))
+            },
+        ],
+        bench: day03::bench,
+        gen_params: day03::gen::PARAMS,
+        generate: day03::gen::generate,
+    },
+    Day {
+        number: 4,
+        dir: "day04",
+        parts: &[|text| solved(day04::part_one::<u64>(text))],
+        bench: day04::bench,
+        gen_params: day04::gen::PARAMS,
+        generate: day04::gen::generate,
+    },
+    Day {
+        number: 5,
+        dir: "day05",
+        parts: &[
+            |text| solved(day05::part_one::<u64>(text)),
+            |text| solved(day05::part_two::<u64>(text)),
+        ],
+        bench: day05::bench,
+        gen_params: day05::gen::PARAMS,
+        generate: day05::gen::generate,
+    },
+    Day {
+        number: 7,
+        dir: "day07",
+        parts: &[
+            |text| solved(day07::part_one::<u64>(text)),
+            |text| solved(day07::part_two::<u64>(text)),
+        ],
+        bench: day07::bench,
+        gen_params: day07::gen::PARAMS,
+        generate: day07::gen::generate,
+    },
+];
+
+// Days can be given as 7, 07 or day07
+pub fn find(name: &str) -> Result<&'static Day, String> {
+    let number = name
+        .strip_prefix("day")
+        .unwrap_or(name)
+        .parse::<u32>()
+        .map_err(|_| format!("not a day: {}", name))?;
+
+    DAYS.iter()
+        .find(|day| day.number == number)
+        .ok_or_else(|| format!("no solution for day {}", number))
+}
+
+#[test]
+fn test_find() {
+    assert_eq!(find("7").unwrap().number, 7);
+    assert_eq!(find("07").unwrap().number, 7);
+    assert_eq!(find("day03").unwrap().number, 3);
+    assert!(find("26").is_err());
+    assert!(find("seven").is_err());
+}
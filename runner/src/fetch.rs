@@ -0,0 +1,131 @@
+// Downloads puzzle inputs into the cache, and installs them into their day's directory.
+//
+//   aoc fetch <day> [--force]
+//
+// An input is only ever downloaded once; --force downloads it again.
+
+use std::path::PathBuf;
+
+use crate::client::{Client, Config};
+use crate::days;
+use crate::http::Request;
+
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    Cache,
+    Server,
+}
+
+pub fn cached_input(config: &Config, day: u32) -> PathBuf {
+    config.day_cache(day).join("input")
+}
+
+pub fn fetch_input(client: &Client, day: u32, force: bool) -> Result<(String, Source), String> {
+    let path = cached_input(&client.config, day);
+
+    if !force {
+        if let Ok(text) = std::fs::read_to_string(&path) {
+            return Ok((text, Source::Cache));
+        }
+    }
+
+    let response = client.send(Request::get(&client.url(&format!("/day/{}/input", day))))?;
+    match response.status {
+        200 => (),
+        404 => return Err(format!("day {} isn't available yet", day)),
+        400 | 500 => {
+            return Err(String::from(
+                "the server refused the session token, is it out of date?",
+            ))
+        }
+        status => {
+            return Err(format!(
+                "unexpected response {} fetching day {}",
+                status, day
+            ))
+        }
+    }
+
+    std::fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    std::fs::write(&path, &response.body).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    return Ok((response.body, Source::Server));
+}
+
+// Copies the input next to the day's code, unless one is already there
+fn install(day: u32, text: &str, force: bool) -> Result<Option<PathBuf>, String> {
+    let dir = days::root().join(format!("day{:02}", day));
+    let path = dir.join("input");
+
+    if !dir.is_dir() || (path.exists() && !force) {
+        return Ok(None);
+    }
+
+    std::fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    return Ok(Some(path));
+}
+
+pub fn fetch(args: &[String]) -> Result<(), String> {
+    let force = args.iter().any(|arg| arg == "--force");
+    let day = match args.iter().find(|arg| *arg != "--force") {
+        Some(day) => day
+            .strip_prefix("day")
+            .unwrap_or(day)
+            .parse::<u32>()
+            .map_err(|_| format!("not a day: {}", day))?,
+        None => return Err(String::from("usage: aoc fetch <day> [--force]")),
+    };
+
+    let client = Client::new(Config::from_env()?);
+    let (text, source) = fetch_input(&client, day, force)?;
+
+    let cached = cached_input(&client.config, day);
+    match source {
+        Source::Cache => println!("day {} input already cached at {}", day, cached.display()),
+        Source::Server => println!("day {} input downloaded to {}", day, cached.display()),
+    }
+
+    if let Some(path) = install(day, &text, force)? {
+        println!("installed as {}", path.display());
+    }
+
+    return Ok(());
+}
+
+#[test]
+fn test_fetch_caches_input() {
+    use crate::client::test_client;
+    use crate::http::mock;
+    use std::time::Duration;
+
+    let server = mock::Server::start(vec![(200, "32T3K 765\n"), (200, "KK677 28\n"), (404, "")]);
+    let client = test_client(&server.url, "fetch", Duration::ZERO);
+
+    assert_eq!(
+        fetch_input(&client, 7, false).unwrap(),
+        (String::from("32T3K 765\n"), Source::Server)
+    );
+    assert_eq!(
+        std::fs::read_to_string(cached_input(&client.config, 7)).unwrap(),
+        "32T3K 765\n"
+    );
+
+    // Served from the cache without a request, until forced
+    assert_eq!(
+        fetch_input(&client, 7, false).unwrap(),
+        (String::from("32T3K 765\n"), Source::Cache)
+    );
+    assert_eq!(
+        fetch_input(&client, 7, true).unwrap(),
+        (String::from("KK677 28\n"), Source::Server)
+    );
+
+    assert!(fetch_input(&client, 25, false).is_err());
+    assert!(!cached_input(&client.config, 25).exists());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].starts_with("GET /2023/day/7/input "));
+    std::fs::remove_dir_all(&client.config.cache).unwrap();
+}
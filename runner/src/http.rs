@@ -0,0 +1,377 @@
+// Just enough HTTP to talk to the puzzle server. Plain http:// URLs, such as a local mock server,
+// are spoken directly over a TcpStream. std has no TLS, so https:// requests are handed to curl.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Request {
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: "GET",
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn post_form(url: &str, fields: &[(&str, &str)]) -> Self {
+        let body = fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        Self {
+            method: "POST",
+            url: url.to_string(),
+            headers: vec![(
+                String::from("Content-Type"),
+                String::from("application/x-www-form-urlencoded"),
+            )],
+            body: Some(body),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn send(&self) -> Result<Response, String> {
+        match self.url.split_once("://") {
+            Some(("http", rest)) => self.send_plain(rest),
+            Some(("https", _)) => self.send_curl(),
+            _ => Err(format!("unsupported url {}", self.url)),
+        }
+    }
+
+    fn send_plain(&self, rest: &str) -> Result<Response, String> {
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let address = match authority.contains(':') {
+            true => authority.to_string(),
+            false => format!("{}:80", authority),
+        };
+
+        let fail = |e: std::io::Error| format!("{}: {}", self.url, e);
+        let mut stream = TcpStream::connect(&address).map_err(fail)?;
+        stream.set_read_timeout(Some(TIMEOUT)).map_err(fail)?;
+
+        // HTTP/1.0 so the response is never chunked and ends when the connection closes
+        let mut head = format!(
+            "{} {} HTTP/1.0\r\nHost: {}\r\n",
+            self.method, path, authority
+        );
+        self.headers
+            .iter()
+            .for_each(|(name, value)| head += &format!("{}: {}\r\n", name, value));
+        if let Some(body) = &self.body {
+            head += &format!("Content-Length: {}\r\n", body.len());
+        }
+        head += "\r\n";
+
+        stream.write_all(head.as_bytes()).map_err(fail)?;
+        if let Some(body) = &self.body {
+            stream.write_all(body.as_bytes()).map_err(fail)?;
+        }
+
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).map_err(fail)?;
+
+        return parse_response(&String::from_utf8_lossy(&raw))
+            .ok_or_else(|| format!("{}: malformed response", self.url));
+    }
+
+    // Headers, the session cookie among them, and the body go to curl as a config file on stdin.
+    // Anything in its arguments can be read by other users through ps.
+    fn curl_args(&self) -> Vec<String> {
+        let mut args = [
+            "--silent",
+            "--show-error",
+            "--max-time",
+            "30",
+            "--config",
+            "-",
+            "--request",
+            self.method,
+            "--write-out",
+            "\n%{http_code}",
+        ]
+        .map(String::from)
+        .to_vec();
+        args.push(self.url.clone());
+
+        return args;
+    }
+
+    fn curl_config(&self) -> String {
+        let mut config = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                format!(
+                    "header = {}\n",
+                    config_string(&format!("{}: {}", name, value))
+                )
+            })
+            .collect::<String>();
+        // data-raw, unlike data-binary, never treats a leading @ as a file name
+        if let Some(body) = &self.body {
+            config += &format!("data-raw = {}\n", config_string(body));
+        }
+
+        return config;
+    }
+
+    fn send_curl(&self) -> Result<Response, String> {
+        let mut child = Command::new("curl")
+            .args(self.curl_args())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("couldn't run curl for https: {}", e))?;
+
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(self.curl_config().as_bytes())
+            .map_err(|e| e.to_string())?;
+
+        let output = child.wait_with_output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(format!(
+                "{}: {}",
+                self.url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        // The status code is written after the body
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or_else(|| format!("{}: malformed response", self.url))?;
+
+        return Ok(Response {
+            status: status
+                .trim()
+                .parse()
+                .map_err(|_| format!("{}: malformed response", self.url))?,
+            body: body.to_string(),
+        });
+    }
+}
+
+fn parse_response(raw: &str) -> Option<Response> {
+    let (head, body) = raw.split_once("\r\n\r\n")?;
+    let status = head
+        .lines()
+        .next()?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()?;
+
+    Some(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+// A quoted value in a curl config file
+fn config_string(s: &str) -> String {
+    let mut out = String::from("\"");
+
+    s.chars().for_each(|c| match c {
+        '"' => out += "\\\"",
+        '\\' => out += "\\\\",
+        '\n' => out += "\\n",
+        '\r' => out += "\\r",
+        '\t' => out += "\\t",
+        c => out.push(c),
+    });
+
+    out + "\""
+}
+
+// Percent encoding for form fields
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(b).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+// A stand-in for the puzzle server in tests. Each connection gets the next canned response, and the
+// raw requests are returned once every response has been used.
+#[cfg(test)]
+pub mod mock {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    pub struct Server {
+        pub url: String,
+        handle: JoinHandle<Vec<String>>,
+    }
+
+    impl Server {
+        pub fn start(responses: Vec<(u16, &str)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let responses = responses
+                .into_iter()
+                .map(|(status, body)| (status, body.to_string()))
+                .collect::<Vec<_>>();
+
+            let handle = std::thread::spawn(move || {
+                responses
+                    .into_iter()
+                    .map(|(status, body)| {
+                        let (mut stream, _) = listener.accept().unwrap();
+                        let request = read_request(&mut stream);
+                        write!(
+                            stream,
+                            "HTTP/1.0 {} Mock\r\nContent-Length: {}\r\n\r\n{}",
+                            status,
+                            body.len(),
+                            body
+                        )
+                        .unwrap();
+                        request
+                    })
+                    .collect()
+            });
+
+            Self { url, handle }
+        }
+
+        pub fn requests(self) -> Vec<String> {
+            self.handle.join().unwrap()
+        }
+    }
+
+    fn read_request(stream: &mut impl Read) -> String {
+        let mut raw = Vec::new();
+        let mut buf = [0; 1024];
+
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            raw.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&raw).to_string();
+
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("Content-Length: "))
+                    .map(|n| n.parse::<usize>().unwrap())
+                    .unwrap_or(0);
+                if body.len() >= length || n == 0 {
+                    return text;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_get() {
+    let server = mock::Server::start(vec![(200, "hello")]);
+
+    let response = Request::get(&format!("{}/2023/day/1/input", server.url))
+        .header("Cookie", "session=abc")
+        .send()
+        .unwrap();
+    assert_eq!(
+        response,
+        Response {
+            status: 200,
+            body: String::from("hello")
+        }
+    );
+
+    let requests = server.requests();
+    assert!(requests[0].starts_with("GET /2023/day/1/input HTTP/1.0\r\n"));
+    assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+}
+
+#[test]
+fn test_parse_response() {
+    assert_eq!(
+        parse_response("HTTP/1.1 429 Too Many Requests\r\nA: b\r\n\r\nslow down"),
+        Some(Response {
+            status: 429,
+            body: String::from("slow down")
+        })
+    );
+    assert_eq!(parse_response("garbage"), None);
+}
+
+#[test]
+fn test_post_form() {
+    let server = mock::Server::start(vec![(404, "")]);
+
+    let response = Request::post_form(
+        &format!("{}/answer", server.url),
+        &[("level", "1"), ("answer", "4 2&")],
+    )
+    .send()
+    .unwrap();
+    assert_eq!(response.status, 404);
+
+    let request = &server.requests()[0];
+    assert!(request.starts_with("POST /answer HTTP/1.0\r\n"));
+    assert!(request.contains("\r\nContent-Type: application/x-www-form-urlencoded\r\n"));
+    assert!(request.ends_with("\r\n\r\nlevel=1&answer=4%202%26"));
+}
+
+#[test]
+fn test_curl_keeps_secrets_off_the_command_line() {
+    let request = Request::post_form("https://example.com/answer", &[("answer", "4\"2")])
+        .header("Cookie", "session=secret-token");
+
+    let args = request.curl_args();
+    assert!(args.iter().all(|arg| !arg.contains("secret-token")));
+    assert!(args.iter().all(|arg| !arg.contains("answer=")));
+    assert_eq!(args.last().unwrap(), "https://example.com/answer");
+
+    assert_eq!(
+        request.curl_config(),
+        "header = \"Content-Type: application/x-www-form-urlencoded\"\n\
+         header = \"Cookie: session=secret-token\"\n\
+         data-raw = \"answer=4%222\"\n"
+    );
+    assert_eq!(config_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+}
+
+#[test]
+fn test_encode() {
+    assert_eq!(encode("abc-123"), "abc-123");
+    assert_eq!(encode("a b&c=d"), "a%20b%26c%3Dd");
+}
@@ -0,0 +1,105 @@
+#![allow(clippy::needless_return)]
+
+// Workspace runner for tasks which cover more than one day.
+//
+//   aoc gen <day> <seed> [--<size> N ...]   print a synthetic puzzle input
+//   aoc verify [day ...] [--input NAME ...] [--record] [--format human|json|tap|junit]
+//                                           check answers against answers.txt
+//   aoc bench [day ...] [--input NAME] [--time MS] [--json FILE]
+//                                           time parsing and solving each day
+//   aoc fetch <day> [--force]               download and cache a day's input
+//   aoc submit <day> <part> [answer]        submit an answer, checking earlier guesses first
+//   aoc new <day>                           scaffold the crate for a new day
+//   aoc watch <day> [--interval MS]         re-run a day whenever its code or inputs change
+//
+// Days are verified concurrently, and some parts split their own work across threads. Passing
+// --single-thread to any command keeps everything on one thread, for benchmarking.
+
+use std::io::Write;
+
+use common::gen::{Rng, Sizes};
+
+mod answers;
+mod bench;
+mod client;
+mod days;
+mod fetch;
+mod http;
+mod new;
+mod report;
+mod submit;
+mod verify;
+mod watch;
+
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(i) = args.iter().position(|arg| arg == "--single-thread") {
+        args.remove(i);
+        common::par::set_single_threaded(true);
+    }
+
+    let result = match args.first().map(String::as_str) {
+        Some("gen") => gen(&args[1..]),
+        Some("verify") => verify::verify(&args[1..]),
+        Some("bench") => bench::bench(&args[1..]),
+        Some("fetch") => fetch::fetch(&args[1..]),
+        Some("submit") => submit::submit(&args[1..]),
+        Some("new") => new::new(&args[1..]),
+        Some("watch") => watch::watch(&args[1..]),
+        _ => Err(usage()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn usage() -> String {
+    String::from(
+        "usage: aoc [--single-thread] <command>
+       aoc gen <day> <seed> [--<size> N ...]
+       aoc verify [day ...] [--input NAME ...] [--record] [--format human|json|tap|junit]
+       aoc bench [day ...] [--input NAME] [--time MS] [--json FILE]
+       aoc fetch <day> [--force]
+       aoc submit <day> <part> [answer]
+       aoc new <day>
+       aoc watch <day> [--interval MS]",
+    )
+}
+
+fn gen(args: &[String]) -> Result<(), String> {
+    let (day, seed, sizes) = match args {
+        [day, seed, sizes @ ..] => (day, seed, sizes),
+        [day] => return Err(gen_help(days::find(day)?)),
+        _ => return Err(usage()),
+    };
+
+    let day = days::find(day)?;
+    let seed = seed
+        .parse()
+        .map_err(|_| format!("seed must be a number, got {}", seed))?;
+    let sizes = Sizes::from_args(day.gen_params, sizes).map_err(|e| e + "\n" + &gen_help(day))?;
+
+    let text = (day.generate)(&mut Rng::new(seed), &sizes);
+    std::io::stdout()
+        .write_all(text.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    return Ok(());
+}
+
+// Lists the size parameters a day's generator takes
+fn gen_help(day: &days::Day) -> String {
+    let params = day
+        .gen_params
+        .iter()
+        .map(|p| format!("  --{:<10} {} (default {})", p.name, p.help, p.default))
+        .collect::<Vec<_>>();
+
+    format!(
+        "usage: aoc gen {} <seed> [--<size> N ...]\n{}",
+        day.number,
+        params.join("\n")
+    )
+}
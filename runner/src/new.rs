@@ -0,0 +1,291 @@
+// Scaffolds the crate for a new day and registers it with the workspace and the runner.
+//
+//   aoc new <day>
+//
+// The crate gets part one and two stubs, a generator stub, and empty example and input files.
+// Nothing is written if the day already exists, in its directory or in any of the registries.
+
+use std::path::{Path, PathBuf};
+
+use crate::days;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.in");
+const MAIN_RS: &str = include_str!("../templates/main.rs.in");
+const LIB_RS: &str = include_str!("../templates/lib.rs.in");
+const GEN_RS: &str = include_str!("../templates/gen.rs.in");
+const DAY_ENTRY: &str = include_str!("../templates/day.rs.in");
+
+fn fill(template: &str, number: u32) -> String {
+    template
+        .replace("{crate}", &format!("day{:02}", number))
+        .replace("{number}", &number.to_string())
+}
+
+// Adds the crate to the workspace's `members = [...]` line
+fn add_member(manifest: &str, name: &str) -> Result<String, String> {
+    let lines = manifest
+        .lines()
+        .map(|line| {
+            let Some(list) = line
+                .strip_prefix("members = [")
+                .and_then(|l| l.strip_suffix(']'))
+            else {
+                return Ok(line.to_string());
+            };
+
+            let mut members = list
+                .split(',')
+                .map(|m| m.trim().trim_matches('"'))
+                .filter(|m| !m.is_empty())
+                .collect::<Vec<_>>();
+            if members.contains(&name) {
+                return Err(format!("{} is already a workspace member", name));
+            }
+            members.push(name);
+            members.sort();
+
+            let members = members
+                .iter()
+                .map(|m| format!("{:?}", m))
+                .collect::<Vec<_>>();
+            Ok(format!("members = [{}]", members.join(", ")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    return Ok(lines.join("\n") + "\n");
+}
+
+// Adds the crate to the runner's dependencies, in order with the other days
+fn add_dependency(manifest: &str, name: &str) -> Result<String, String> {
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("day"))
+        .map(|(i, line)| (i, line.split(' ').next().unwrap()))
+        .collect::<Vec<_>>();
+
+    if days.iter().any(|(_, day)| *day == name) {
+        return Err(format!("the runner already depends on {}", name));
+    }
+    let at = days
+        .iter()
+        .find(|(_, day)| *day > name)
+        .or(days.last())
+        .map(|(i, day)| if *day > name { *i } else { i + 1 })
+        .ok_or("no day dependencies found in the runner's Cargo.toml")?;
+
+    let dependency = format!("{} = {{ path = \"../{}\" }}", name, name);
+    lines.insert(at, &dependency);
+
+    return Ok(lines.join("\n") + "\n");
+}
+
+// Adds the day to the runner's list of days, in order
+fn add_day(source: &str, number: u32) -> Result<String, String> {
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))
+        .ok_or("no list of days found in days.rs")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == "];")
+            .ok_or("the list of days in days.rs isn't closed")?;
+
+    let mut at = end;
+    for i in (start..end).filter(|i| lines[*i] == "    Day {") {
+        let existing = lines[i + 1]
+            .trim()
+            .strip_prefix("number: ")
+            .and_then(|n| n.strip_suffix(','))
+            .and_then(|n| n.parse::<u32>().ok());
+
+        match existing {
+            Some(n) if n == number => return Err(format!("day {} is already registered", number)),
+            Some(n) if n > number => {
+                at = i;
+                break;
+            }
+            _ => (),
+        }
+    }
+
+    let entry = fill(DAY_ENTRY, number);
+    lines.splice(at..at, entry.lines());
+
+    return Ok(lines.join("\n") + "\n");
+}
+
+// Writes the new crate under `root` and registers it, returning every file written
+pub fn scaffold(root: &Path, number: u32) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&number) {
+        return Err(format!("there is no day {}", number));
+    }
+
+    let name = format!("day{:02}", number);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("runner").join("Cargo.toml");
+    let registry = root.join("runner").join("src").join("days.rs");
+
+    // Everything is checked before anything is written
+    let updates = [
+        (workspace.clone(), add_member(&read(&workspace)?, &name)?),
+        (runner.clone(), add_dependency(&read(&runner)?, &name)?),
+        (registry.clone(), add_day(&read(&registry)?, number)?),
+    ];
+    let files = [
+        (dir.join("Cargo.toml"), fill(CARGO_TOML, number)),
+        (dir.join("src").join("main.rs"), fill(MAIN_RS, number)),
+        (dir.join("src").join("lib.rs"), fill(LIB_RS, number)),
+        (dir.join("src").join("gen.rs"), fill(GEN_RS, number)),
+        (dir.join("example"), String::new()),
+        (dir.join("input"), String::new()),
+    ];
+
+    std::fs::create_dir_all(dir.join("src")).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    files
+        .iter()
+        .chain(updates.iter())
+        .map(|(path, text)| {
+            std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+            Ok(path.clone())
+        })
+        .collect()
+}
+
+pub fn new(args: &[String]) -> Result<(), String> {
+    let [day] = args else {
+        return Err(String::from("usage: aoc new <day>"));
+    };
+    let number = day
+        .strip_prefix("day")
+        .unwrap_or(day)
+        .parse::<u32>()
+        .map_err(|_| format!("not a day: {}", day))?;
+
+    let root = days::root();
+    for path in scaffold(&root, number)? {
+        println!("wrote {}", path.strip_prefix(&root).unwrap().display());
+    }
+    println!("rebuild the runner to pick up day {}", number);
+
+    return Ok(());
+}
+
+// A registry with days 5 and 7, the way days.rs lays it out
+#[cfg(test)]
+const TEST_DAYS: &str = "pub const DAYS: &[Day] = &[
+    Day {
+        number: 5,
+        dir: \"day05\",
+    },
+    Day {
+        number: 7,
+        dir: \"day07\",
+    },
+];
+";
+
+#[cfg(test)]
+fn test_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("runner").join("src")).unwrap();
+
+    let files = [
+        (
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"common\", \"day05\", \"day07\", \"runner\"]\n",
+        ),
+        (
+            "runner/Cargo.toml",
+            "[dependencies]\nday05 = { path = \"../day05\" }\nday07 = { path = \"../day07\" }\n",
+        ),
+        ("runner/src/days.rs", TEST_DAYS),
+    ];
+    for (file, text) in files {
+        std::fs::write(root.join(file), text).unwrap();
+    }
+
+    return root;
+}
+
+#[test]
+fn test_add_member() {
+    let manifest = "[workspace]\nmembers = [\"common\", \"day01\", \"day07\", \"runner\"]\n";
+
+    assert_eq!(
+        add_member(manifest, "day06").unwrap(),
+        "[workspace]\nmembers = [\"common\", \"day01\", \"day06\", \"day07\", \"runner\"]\n"
+    );
+    assert!(add_member(manifest, "day07").is_err());
+}
+
+#[test]
+fn test_add_dependency() {
+    let manifest = "[dependencies]\ncommon = { path = \"../common\" }\nday02 = { path = \"../day02\" }\nday04 = { path = \"../day04\" }\n";
+
+    assert!(add_dependency(manifest, "day03")
+        .unwrap()
+        .contains("day02 = { path = \"../day02\" }\nday03 = { path = \"../day03\" }\nday04"));
+    assert!(add_dependency(manifest, "day09")
+        .unwrap()
+        .ends_with("day04 = { path = \"../day04\" }\nday09 = { path = \"../day09\" }\n"));
+    assert!(add_dependency(manifest, "day04").is_err());
+}
+
+#[test]
+fn test_add_day() {
+    let updated = add_day(TEST_DAYS, 6).unwrap();
+    let day05 = updated.find("dir: \"day05\"").unwrap();
+    let day06 = updated.find("dir: \"day06\"").unwrap();
+    let day07 = updated.find("dir: \"day07\"").unwrap();
+    assert!(day05 < day06 && day06 < day07);
+    assert!(updated.contains("        bench: day06::bench,\n"));
+
+    assert!(add_day(TEST_DAYS, 7).is_err());
+    assert!(add_day(TEST_DAYS, 9)
+        .unwrap()
+        .ends_with("        generate: day09::gen::generate,\n    },\n];\n"));
+}
+
+#[test]
+fn test_scaffold() {
+    let root = test_root("new");
+
+    let written = scaffold(&root, 6).unwrap();
+    assert_eq!(written.len(), 9);
+    assert_eq!(
+        std::fs::read_to_string(root.join("day06/example")).unwrap(),
+        ""
+    );
+    assert!(std::fs::read_to_string(root.join("day06/src/main.rs"))
+        .unwrap()
+        .contains("use day06::{part_one, part_two};"));
+    assert!(std::fs::read_to_string(root.join("Cargo.toml"))
+        .unwrap()
+        .contains("\"day05\", \"day06\", \"day07\""));
+
+    // Refuses to touch a day that exists
+    let main = std::fs::read_to_string(root.join("day06/src/main.rs")).unwrap();
+    assert!(scaffold(&root, 6).is_err());
+    assert_eq!(
+        std::fs::read_to_string(root.join("day06/src/main.rs")).unwrap(),
+        main
+    );
+    assert!(scaffold(&root, 7).unwrap_err().contains("already"));
+    assert!(!root.join("day07").exists());
+    assert!(scaffold(&root, 26).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
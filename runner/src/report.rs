@@ -0,0 +1,271 @@
+// Formats verify results for people and for tools: the human table, JSON lines, TAP, or JUnit XML.
+// Every format carries the day, part, input, answer, solve time and verification status.
+
+use common::bench::format_duration;
+use common::json;
+
+use crate::verify::{Outcome, Status};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Human,
+    Json,
+    Tap,
+    Junit,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "tap" => Ok(Self::Tap),
+            "junit" => Ok(Self::Junit),
+            other => Err(format!(
+                "unknown format {}, expected human, json, tap or junit",
+                other
+            )),
+        }
+    }
+}
+
+// `recorded` is whether unrecorded answers are being written to the registry
+pub fn render(format: Format, outcomes: &[Outcome], recorded: bool) -> String {
+    match format {
+        Format::Human => human(outcomes, recorded),
+        Format::Json => json(outcomes, recorded),
+        Format::Tap => tap(outcomes, recorded),
+        Format::Junit => junit(outcomes, recorded),
+    }
+}
+
+fn status_name(status: &Status, recorded: bool) -> &'static str {
+    match status {
+        Status::Match => "match",
+        Status::Regression { .. } => "regression",
+        Status::Unrecorded if recorded => "recorded",
+        Status::Unrecorded => "unrecorded",
+        Status::Failed(_) => "failed",
+    }
+}
+
+fn human(outcomes: &[Outcome], recorded: bool) -> String {
+    outcomes
+        .iter()
+        .map(|o| {
+            let status = match &o.status {
+                Status::Match => String::from("ok"),
+                Status::Regression { expected } => format!("REGRESSION, expected {}", expected),
+                Status::Failed(e) => format!("FAILED, {}", e),
+                unrecorded => String::from(status_name(unrecorded, recorded)),
+            };
+
+            format!(
+                "day {:>2} part {} {:<8} {:>16}  {:>8}  {}\n",
+                o.day,
+                o.part,
+                o.input,
+                o.answer.as_deref().unwrap_or("-"),
+                format_duration(o.elapsed),
+                status
+            )
+        })
+        .collect()
+}
+
+// One object per line
+fn json(outcomes: &[Outcome], recorded: bool) -> String {
+    outcomes
+        .iter()
+        .map(|o| {
+            let mut fields = vec![
+                format!("\"day\":{}", o.day),
+                format!("\"part\":{}", o.part),
+                format!("\"input\":{}", json::string(&o.input)),
+                format!(
+                    "\"answer\":{}",
+                    o.answer
+                        .as_deref()
+                        .map_or(String::from("null"), json::string)
+                ),
+                format!("\"time_ns\":{}", o.elapsed.as_nanos()),
+                format!("\"status\":\"{}\"", status_name(&o.status, recorded)),
+            ];
+            match &o.status {
+                Status::Regression { expected } => {
+                    fields.push(format!("\"expected\":{}", json::string(expected)))
+                }
+                Status::Failed(e) => fields.push(format!("\"error\":{}", json::string(e))),
+                _ => (),
+            }
+
+            format!("{{{}}}\n", fields.join(","))
+        })
+        .collect()
+}
+
+// TAP version 13. Unrecorded answers pass as skipped, since there's nothing to check them against.
+fn tap(outcomes: &[Outcome], recorded: bool) -> String {
+    let mut out = format!("TAP version 13\n1..{}\n", outcomes.len());
+
+    outcomes.iter().enumerate().for_each(|(i, o)| {
+        let description = format!(
+            "day {} part {} {}: {} ({})",
+            o.day,
+            o.part,
+            o.input,
+            o.answer.as_deref().unwrap_or("-"),
+            format_duration(o.elapsed)
+        );
+
+        out += &match &o.status {
+            Status::Match => format!("ok {} - {}\n", i + 1, description),
+            Status::Unrecorded => format!(
+                "ok {} - {} # SKIP {}\n",
+                i + 1,
+                description,
+                status_name(&o.status, recorded)
+            ),
+            Status::Regression { expected } => format!(
+                "not ok {} - {}\n  ---\n  expected: {}\n  got: {}\n  ...\n",
+                i + 1,
+                description,
+                json::string(expected),
+                json::string(o.answer.as_deref().unwrap_or_default())
+            ),
+            Status::Failed(e) => format!(
+                "not ok {} - {}\n  ---\n  error: {}\n  ...\n",
+                i + 1,
+                description,
+                json::string(e)
+            ),
+        };
+    });
+
+    return out;
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// One test suite, with a test case per day, part and input
+fn junit(outcomes: &[Outcome], recorded: bool) -> String {
+    let count = |f: fn(&Status) -> bool| outcomes.iter().filter(|o| f(&o.status)).count();
+    let seconds = |o: &Outcome| format!("{:.6}", o.elapsed.as_secs_f64());
+
+    let mut out = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n  <testsuite name=\"aoc verify\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+        outcomes.len(),
+        count(|s| matches!(s, Status::Regression { .. })),
+        count(|s| matches!(s, Status::Failed(_))),
+        count(|s| matches!(s, Status::Unrecorded)),
+        outcomes.iter().map(|o| o.elapsed.as_secs_f64()).sum::<f64>()
+    );
+
+    outcomes.iter().for_each(|o| {
+        out += &format!(
+            "    <testcase classname=\"day{:02}\" name=\"part {} {}\" time=\"{}\">\n",
+            o.day,
+            o.part,
+            xml_escape(&o.input),
+            seconds(o)
+        );
+        out += &match &o.status {
+            Status::Match => String::new(),
+            Status::Unrecorded => format!(
+                "      <skipped message=\"{}\"/>\n",
+                status_name(&o.status, recorded)
+            ),
+            Status::Regression { expected } => format!(
+                "      <failure message=\"expected {}, got {}\"/>\n",
+                xml_escape(expected),
+                xml_escape(o.answer.as_deref().unwrap_or_default())
+            ),
+            Status::Failed(e) => format!("      <error message=\"{}\"/>\n", xml_escape(e)),
+        };
+        if let Some(answer) = &o.answer {
+            out += &format!("      <system-out>{}</system-out>\n", xml_escape(answer));
+        }
+        out += "    </testcase>\n";
+    });
+
+    return out + "  </testsuite>\n</testsuites>\n";
+}
+
+#[allow(dead_code)]
+fn outcomes() -> Vec<Outcome> {
+    use std::time::Duration;
+
+    let outcome = |part, answer: Option<&str>, status| Outcome {
+        day: 7,
+        part,
+        input: String::from("example"),
+        answer: answer.map(String::from),
+        elapsed: Duration::from_micros(1500),
+        status,
+    };
+
+    vec![
+        outcome(1, Some("6440"), Status::Match),
+        outcome(
+            2,
+            Some("5905"),
+            Status::Regression {
+                expected: String::from("59\"05"),
+            },
+        ),
+        outcome(1, Some("1"), Status::Unrecorded),
+        outcome(2, None, Status::Failed(String::from("panicked: <oops>"))),
+    ]
+}
+
+#[test]
+fn test_format_parse() {
+    assert_eq!(Format::parse("tap"), Ok(Format::Tap));
+    assert!(Format::parse("yaml").is_err());
+}
+
+#[test]
+fn test_json() {
+    let lines = render(Format::Json, &outcomes(), false);
+    let lines = lines.lines().collect::<Vec<_>>();
+
+    assert_eq!(
+        lines[0],
+        "{\"day\":7,\"part\":1,\"input\":\"example\",\"answer\":\"6440\",\"time_ns\":1500000,\"status\":\"match\"}"
+    );
+    assert!(lines[1].ends_with("\"status\":\"regression\",\"expected\":\"59\\\"05\"}"));
+    assert!(lines[2].contains("\"status\":\"unrecorded\""));
+    assert!(lines[3].contains("\"answer\":null,"));
+    assert!(lines[3].ends_with("\"error\":\"panicked: <oops>\"}"));
+
+    assert!(render(Format::Json, &outcomes(), true).contains("\"status\":\"recorded\""));
+}
+
+#[test]
+fn test_tap() {
+    let tap = render(Format::Tap, &outcomes(), false);
+
+    assert!(tap.starts_with("TAP version 13\n1..4\nok 1 - day 7 part 1 example: 6440 (1.50ms)\n"));
+    assert!(tap.contains("not ok 2 - day 7 part 2 example: 5905 (1.50ms)\n  ---\n  expected: \"59\\\"05\"\n  got: \"5905\"\n  ...\n"));
+    assert!(tap.contains("ok 3 - day 7 part 1 example: 1 (1.50ms) # SKIP unrecorded\n"));
+    assert!(tap.contains("not ok 4 - day 7 part 2 example: - (1.50ms)\n"));
+}
+
+#[test]
+fn test_junit() {
+    let xml = render(Format::Junit, &outcomes(), false);
+
+    assert!(xml.contains("tests=\"4\" failures=\"1\" errors=\"1\" skipped=\"1\" time=\"0.006000\""));
+    assert!(xml.contains(
+        "<testcase classname=\"day07\" name=\"part 1 example\" time=\"0.001500\">\n      <system-out>6440</system-out>\n    </testcase>"
+    ));
+    assert!(xml.contains("<failure message=\"expected 59&quot;05, got 5905\"/>"));
+    assert!(xml.contains("<error message=\"panicked: &lt;oops&gt;\"/>"));
+    assert!(xml.ends_with("</testsuite>\n</testsuites>\n"));
+}
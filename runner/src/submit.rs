@@ -0,0 +1,509 @@
+// Submits answers to the puzzle server, keeping a history of every guess.
+//
+//   aoc submit <day> <part> [answer]
+//
+// Without an answer the day is solved on its input first. Before anything is sent the guess is
+// checked against the day's history: a part already solved, an answer already rejected, one
+// outside the bounds left by earlier "too high" and "too low" responses, or a guess made while the
+// server still wants us to wait is refused without asking the server. Correct answers are recorded
+// in answers.txt.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::answers::Answers;
+use crate::client::{Client, Config};
+use crate::days;
+use crate::http::Request;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    // Wrong without saying which way
+    Wrong,
+    // Answered too recently, nothing was checked
+    TooSoon,
+    // The part has already been solved, or isn't unlocked
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    const NAMES: &'static [(Verdict, &'static str)] = &[
+        (Verdict::Correct, "correct"),
+        (Verdict::TooHigh, "too-high"),
+        (Verdict::TooLow, "too-low"),
+        (Verdict::Wrong, "wrong"),
+        (Verdict::TooSoon, "too-soon"),
+        (Verdict::WrongLevel, "wrong-level"),
+        (Verdict::Unknown, "unknown"),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(v, _)| v.clone())
+    }
+
+    fn name(&self) -> &'static str {
+        Self::NAMES.iter().find(|(v, _)| v == self).unwrap().1
+    }
+}
+
+// What the server made of a guess, and how many seconds it wants us to wait before the next one.
+pub fn parse_response(body: &str) -> (Verdict, u64) {
+    let verdict = match body {
+        b if b.contains("That's the right answer") => Verdict::Correct,
+        b if b.contains("answer is too high") => Verdict::TooHigh,
+        b if b.contains("answer is too low") => Verdict::TooLow,
+        b if b.contains("That's not the right answer") => Verdict::Wrong,
+        b if b.contains("You gave an answer too recently") => Verdict::TooSoon,
+        b if b.contains("solving the right level") => Verdict::WrongLevel,
+        _ => Verdict::Unknown,
+    };
+
+    return (verdict, parse_wait(body));
+}
+
+// Understands "you have 1m 5s left to wait" and "please wait 5 minutes before trying again"
+fn parse_wait(body: &str) -> u64 {
+    let body = body.to_lowercase();
+
+    if let Some(end) = body.find(" left to wait") {
+        let start = body[..end].rfind("you have ").map_or(0, |i| i + 9);
+
+        return body[start..end]
+            .split_whitespace()
+            .filter_map(|t| {
+                let (n, unit) = match (
+                    t.strip_suffix('h'),
+                    t.strip_suffix('m'),
+                    t.strip_suffix('s'),
+                ) {
+                    (Some(n), _, _) => (n, 3600),
+                    (_, Some(n), _) => (n, 60),
+                    (_, _, Some(n)) => (n, 1),
+                    _ => return None,
+                };
+
+                n.parse::<u64>().ok().map(|n| n * unit)
+            })
+            .sum();
+    }
+
+    if let Some(start) = body.find("please wait ") {
+        let mut words = body[start + 12..].split_whitespace();
+        let n = match words.next() {
+            Some("one") => 1,
+            Some(n) => n.parse().unwrap_or(0),
+            None => 0,
+        };
+
+        return match words.next() {
+            Some(unit) if unit.starts_with("minute") => n * 60,
+            Some(unit) if unit.starts_with("second") => n,
+            _ => 0,
+        };
+    }
+
+    return 0;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    // Unix time in seconds
+    pub at: u64,
+    // Seconds to wait before guessing again
+    pub wait: u64,
+}
+
+// Every guess for one day, stored one per line as `<part> <at> <verdict> <wait> <answer>`.
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    pub guesses: Vec<Guess>,
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let guesses = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let invalid = || format!("malformed guess: {}", line);
+                let fields = line.splitn(5, ' ').collect::<Vec<_>>();
+                let [part, at, verdict, wait, answer] = fields[..] else {
+                    return Err(invalid());
+                };
+
+                Ok(Guess {
+                    part: part.parse().map_err(|_| invalid())?,
+                    at: at.parse().map_err(|_| invalid())?,
+                    verdict: Verdict::from_name(verdict).ok_or_else(invalid)?,
+                    wait: wait.parse().map_err(|_| invalid())?,
+                    answer: answer.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        return Ok(Self { guesses });
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+        std::fs::write(path, self.to_string()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    // Why a guess shouldn't be sent, if it shouldn't
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        // The history and answers.txt are both split on whitespace
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            return Err(format!(
+                "{:?} isn't a single word, so can't be an answer",
+                answer
+            ));
+        }
+
+        if let Some(last) = self.guesses.last() {
+            if now < last.at + last.wait {
+                return Err(format!(
+                    "the server wants another {}s before the next guess",
+                    last.at + last.wait - now
+                ));
+            }
+        }
+
+        let guesses = self.guesses.iter().filter(|g| g.part == part);
+
+        for g in guesses.clone() {
+            match g.verdict {
+                Verdict::Correct => {
+                    return Err(format!(
+                        "part {} was already solved with {}",
+                        part, g.answer
+                    ))
+                }
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong if g.answer == answer => {
+                    return Err(format!(
+                        "{} was already guessed and was {}",
+                        answer, g.verdict
+                    ))
+                }
+                _ => (),
+            }
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |verdict| {
+            guesses
+                .clone()
+                .filter(move |g| g.verdict == verdict)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Verdict::TooHigh).min() {
+            if value >= high {
+                return Err(format!("{} is too high, {} already was", answer, high));
+            }
+        }
+        if let Some(low) = bound(Verdict::TooLow).max() {
+            if value <= low {
+                return Err(format!("{} is too low, {} already was", answer, low));
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name().replace('-', " "))
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.guesses.iter().try_for_each(|g| {
+            writeln!(
+                f,
+                "{} {} {} {} {}",
+                g.part,
+                g.at,
+                g.verdict.name(),
+                g.wait,
+                g.answer
+            )
+        })
+    }
+}
+
+pub fn history_path(config: &Config, day: u32) -> PathBuf {
+    config.day_cache(day).join("guesses")
+}
+
+pub fn submit_answer(
+    client: &Client,
+    answers_path: &Path,
+    day: u32,
+    part: u8,
+    answer: &str,
+) -> Result<Guess, String> {
+    let path = history_path(&client.config, day);
+    let mut history = History::load(&path)?;
+    history.check(part, answer, now())?;
+
+    let request = Request::post_form(
+        &client.url(&format!("/day/{}/answer", day)),
+        &[("level", &part.to_string()), ("answer", answer)],
+    );
+    let response = client.send(request)?;
+    if response.status != 200 {
+        return Err(format!(
+            "unexpected response {} submitting day {}",
+            response.status, day
+        ));
+    }
+
+    let (verdict, wait) = parse_response(&response.body);
+    let guess = Guess {
+        part,
+        answer: answer.to_string(),
+        verdict,
+        at: now(),
+        wait,
+    };
+
+    history.guesses.push(guess.clone());
+    history.save(&path)?;
+
+    if guess.verdict == Verdict::Correct {
+        let mut answers = Answers::load(answers_path)?;
+        answers.record(day, part, "input", answer);
+        answers.save(answers_path)?;
+    }
+
+    return Ok(guess);
+}
+
+pub fn submit(args: &[String]) -> Result<(), String> {
+    let usage = || String::from("usage: aoc submit <day> <part> [answer]");
+    let (day, part, answer) = match args {
+        [day, part] => (day, part, None),
+        [day, part, answer] => (day, part, Some(answer.clone())),
+        _ => return Err(usage()),
+    };
+    let part = part
+        .parse::<u8>()
+        .ok()
+        .filter(|p| (1..=2).contains(p))
+        .ok_or_else(usage)?;
+
+    // Days without a solution yet can still be submitted by hand
+    let day = match days::find(day) {
+        Ok(day) => Ok(day),
+        Err(_) if answer.is_some() => Err(day
+            .strip_prefix("day")
+            .unwrap_or(day)
+            .parse::<u32>()
+            .map_err(|_| usage())?),
+        Err(e) => return Err(e),
+    };
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let day = day.as_ref().unwrap();
+            let solve = day
+                .parts
+                .get(part as usize - 1)
+                .ok_or_else(|| format!("day {} has no part {} yet", day.number, part))?;
+            solve(&day.read("input")?)?
+        }
+    };
+    let number = match &day {
+        Ok(day) => day.number,
+        Err(number) => *number,
+    };
+
+    let client = Client::new(Config::from_env()?);
+    let guess = submit_answer(
+        &client,
+        &days::root().join("answers.txt"),
+        number,
+        part,
+        &answer,
+    )?;
+
+    println!(
+        "day {} part {}: {} is {}",
+        number, part, answer, guess.verdict
+    );
+    if guess.wait > 0 {
+        println!("wait {}s before the next guess", guess.wait);
+    }
+
+    return Ok(());
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[allow(dead_code)]
+fn guess(part: u8, answer: &str, verdict: Verdict) -> Guess {
+    Guess {
+        part,
+        answer: answer.to_string(),
+        verdict,
+        at: 1000,
+        wait: 0,
+    }
+}
+
+#[test]
+fn test_parse_response() {
+    let page = |s: &str| format!("<main><article><p>{}</p></article></main>", s);
+
+    assert_eq!(
+        parse_response(&page(
+            "That's the right answer! You are one gold star closer."
+        )),
+        (Verdict::Correct, 0)
+    );
+    assert_eq!(
+        parse_response(&page("That's not the right answer; your answer is too high. Please wait one minute before trying again.")),
+        (Verdict::TooHigh, 60)
+    );
+    assert_eq!(
+        parse_response(&page("That's not the right answer; your answer is too low. Because you have guessed incorrectly 5 times on this puzzle, please wait 5 minutes before trying again.")),
+        (Verdict::TooLow, 300)
+    );
+    assert_eq!(
+        parse_response(&page("That's not the right answer. If you're stuck, make sure you're using the full input data.")),
+        (Verdict::Wrong, 0)
+    );
+    assert_eq!(
+        parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.")),
+        (Verdict::TooSoon, 65)
+    );
+    assert_eq!(parse_wait("You have 1h 2m ★ é 5s left to wait."), 3725);
+    assert_eq!(
+        parse_response(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        )),
+        (Verdict::WrongLevel, 0)
+    );
+    assert_eq!(parse_response("<html></html>"), (Verdict::Unknown, 0));
+}
+
+#[test]
+fn test_history_refuses_contradictions() {
+    let history = History {
+        guesses: vec![
+            guess(1, "500", Verdict::TooHigh),
+            guess(1, "100", Verdict::TooLow),
+            guess(1, "abc", Verdict::Wrong),
+            guess(2, "42", Verdict::Correct),
+        ],
+    };
+
+    assert!(history.check(1, "300", 2000).is_ok());
+    assert!(history.check(1, "500", 2000).is_err());
+    assert!(history.check(1, "501", 2000).is_err());
+    assert!(history.check(1, "100", 2000).is_err());
+    assert!(history.check(1, "-3", 2000).is_err());
+    assert!(history.check(1, "abc", 2000).is_err());
+    assert!(history.check(1, "abd", 2000).is_ok());
+    assert!(history.check(1, "3 4", 2000).is_err());
+    assert!(history.check(1, "", 2000).is_err());
+    assert!(history
+        .check(2, "43", 2000)
+        .unwrap_err()
+        .contains("already solved"));
+}
+
+#[test]
+fn test_history_waits() {
+    let mut history = History::default();
+    history.guesses.push(Guess {
+        wait: 60,
+        ..guess(1, "7", Verdict::Wrong)
+    });
+
+    assert!(history.check(1, "8", 1030).unwrap_err().contains("30s"));
+    assert!(history.check(1, "8", 1060).is_ok());
+}
+
+#[test]
+fn test_history_round_trip() {
+    let history = History {
+        guesses: vec![
+            guess(1, "500", Verdict::TooHigh),
+            Guess {
+                wait: 65,
+                ..guess(2, "a b", Verdict::TooSoon)
+            },
+        ],
+    };
+
+    let text = history.to_string();
+    assert_eq!(text, "1 1000 too-high 0 500\n2 1000 too-soon 65 a b\n");
+    assert_eq!(History::parse(&text).unwrap(), history);
+    assert!(History::parse("1 1000 maybe 0 5").is_err());
+}
+
+#[test]
+fn test_submit_answer() {
+    use crate::client::test_client;
+    use crate::http::mock;
+    use std::time::Duration;
+
+    let server = mock::Server::start(vec![
+        (200, "That's not the right answer; your answer is too low."),
+        (200, "That's the right answer!"),
+    ]);
+    let client = test_client(&server.url, "submit", Duration::ZERO);
+    let answers = client.config.cache.join("answers.txt");
+
+    let first = submit_answer(&client, &answers, 7, 2, "100").unwrap();
+    assert_eq!(first.verdict, Verdict::TooLow);
+
+    // Refused without a request
+    assert!(submit_answer(&client, &answers, 7, 2, "99").is_err());
+
+    let second = submit_answer(&client, &answers, 7, 2, "251421071").unwrap();
+    assert_eq!(second.verdict, Verdict::Correct);
+    assert_eq!(
+        Answers::load(&answers).unwrap().get(7, 2, "input"),
+        Some("251421071")
+    );
+
+    let history = History::load(&history_path(&client.config, 7)).unwrap();
+    assert_eq!(history.guesses.len(), 2);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].starts_with("POST /2023/day/7/answer "));
+    assert!(requests[0].ends_with("level=2&answer=100"));
+    std::fs::remove_dir_all(&client.config.cache).unwrap();
+}
@@ -0,0 +1,217 @@
+// Checks each day's answers against the known answers registry.
+//
+//   aoc verify [day ...] [--input NAME ...] [--record] [--format human|json|tap|junit]
+//
+// Every part is run on each of the day's input files, or just the named ones. An answer which differs from the recorded
+// one is a regression; one with nothing recorded is reported, and written to the registry with
+// --record.
+//
+// Days are run concurrently, each on its own thread, but the results are reported in order. Solve
+// times are therefore measured while other days compete for the cores, and days which split their
+// own work across threads can briefly run more threads than there are cores, so they are only a
+// rough guide. Use `aoc --single-thread verify` to run one day at a time, or `aoc bench` for
+// timings worth comparing.
+
+use std::time::{Duration, Instant};
+
+use common::par;
+
+use crate::answers::Answers;
+use crate::days::{self, Day, DAYS};
+use crate::report::{self, Format};
+
+const INPUTS: &[&str] = &["example", "input"];
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Match,
+    Regression { expected: String },
+    Unrecorded,
+    Failed(String),
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub day: u32,
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+    // How long solving took, not counting reading the input
+    pub elapsed: Duration,
+    pub status: Status,
+}
+
+pub fn check(day: &Day, part: u8, input: &str, answers: &Answers) -> Outcome {
+    let solve = day.parts[part as usize - 1];
+    let mut elapsed = Duration::ZERO;
+
+    // A day which panics on one input shouldn't stop the others being checked
+    let result = day.read(input).and_then(|text| {
+        let start = Instant::now();
+        let result = std::panic::catch_unwind(|| solve(&text));
+        elapsed = start.elapsed();
+
+        result.unwrap_or_else(|panic| Err(panic_message(panic)))
+    });
+
+    let (answer, status) = match result {
+        Err(e) => (None, Status::Failed(e)),
+        Ok(answer) => {
+            let status = match answers.get(day.number, part, input) {
+                None => Status::Unrecorded,
+                Some(expected) if expected == answer => Status::Match,
+                Some(expected) => Status::Regression {
+                    expected: expected.to_string(),
+                },
+            };
+            (Some(answer), status)
+        }
+    };
+
+    return Outcome {
+        day: day.number,
+        part,
+        input: input.to_string(),
+        answer,
+        elapsed,
+        status,
+    };
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    let message = match panic.downcast_ref::<String>() {
+        Some(s) => s.as_str(),
+        None => panic
+            .downcast_ref::<&str>()
+            .copied()
+            .unwrap_or("unknown cause"),
+    };
+
+    format!("panicked: {}", message)
+}
+
+// Runs every part of the given days on each of the inputs they have, spread over `threads`
+pub fn check_days(
+    threads: usize,
+    days: &[&Day],
+    inputs: &[&str],
+    answers: &Answers,
+) -> Vec<Outcome> {
+    par::map_with(threads, days, |day| {
+        inputs
+            .iter()
+            .filter(|input| day.input(input).exists())
+            .flat_map(|input| {
+                (1..=day.parts.len() as u8).map(move |part| check(day, part, input, answers))
+            })
+            .collect::<Vec<_>>()
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub fn verify(args: &[String]) -> Result<(), String> {
+    let mut record = false;
+    let mut format = Format::Human;
+    let mut names = Vec::new();
+    let mut inputs = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => record = true,
+            "--input" => inputs.push(args.next().ok_or("missing value for --input")?.as_str()),
+            "--format" => format = Format::parse(args.next().ok_or("missing value for --format")?)?,
+            name => names.push(name),
+        }
+    }
+
+    let days = match names.is_empty() {
+        true => DAYS.iter().collect(),
+        false => names
+            .into_iter()
+            .map(days::find)
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let path = days::root().join("answers.txt");
+    let mut answers = Answers::load(&path)?;
+    if inputs.is_empty() {
+        inputs = INPUTS.to_vec();
+    }
+    let outcomes = check_days(par::threads(), &days, &inputs, &answers);
+
+    print!("{}", report::render(format, &outcomes, record));
+
+    if record {
+        outcomes
+            .iter()
+            .filter(|o| o.status == Status::Unrecorded)
+            .for_each(|o| {
+                answers.record(o.day, o.part, &o.input, o.answer.as_ref().unwrap());
+            });
+        answers.save(&path)?;
+    }
+
+    let bad = outcomes
+        .iter()
+        .filter(|o| matches!(o.status, Status::Regression { .. } | Status::Failed(_)))
+        .count();
+
+    match bad {
+        0 => Ok(()),
+        n => Err(format!("{} answer(s) don't match", n)),
+    }
+}
+
+#[allow(dead_code)]
+fn assert_recorded(day: u32, part: u8, input: &str, expected: &str) {
+    let day = days::find(&day.to_string()).unwrap();
+    let mut answers = Answers::default();
+    answers.record(day.number, part, input, expected);
+
+    let outcome = check(day, part, input, &answers);
+    assert_eq!(outcome.status, Status::Match, "{:?}", outcome);
+}
+
+// One test per entry in answers.txt, written by the build script
+include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+
+#[test]
+fn test_check_statuses() {
+    let day = days::find("7").unwrap();
+    let mut answers = Answers::default();
+    answers.record(7, 1, "example", "6440");
+    answers.record(7, 2, "example", "1");
+
+    assert_eq!(check(day, 1, "example", &answers).status, Status::Match);
+    assert_eq!(
+        check(day, 2, "example", &answers).status,
+        Status::Regression {
+            expected: String::from("1")
+        }
+    );
+    assert_eq!(check(day, 1, "input", &answers).status, Status::Unrecorded);
+    assert!(matches!(
+        check(day, 1, "missing", &answers).status,
+        Status::Failed(_)
+    ));
+}
+
+#[test]
+fn test_single_threaded_matches() {
+    let days = DAYS.iter().collect::<Vec<_>>();
+    let answers = Answers::default();
+    let answers_of = |outcomes: Vec<Outcome>| {
+        outcomes
+            .into_iter()
+            .map(|o| (o.day, o.part, o.input, o.answer))
+            .collect::<Vec<_>>()
+    };
+
+    let concurrent = answers_of(check_days(days.len(), &days, INPUTS, &answers));
+    let single = answers_of(check_days(1, &days, INPUTS, &answers));
+
+    assert_eq!(concurrent, single);
+}
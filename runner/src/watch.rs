@@ -0,0 +1,336 @@
+// Re-runs a day whenever its code or inputs change.
+//
+//   aoc watch <day> [--interval MS]
+//
+// The day's src/, Cargo.toml, example and input are polled for changes, along with the common crate
+// every day builds on. Each change rebuilds and runs `aoc verify` for the day through cargo, on
+// just the changed inputs if nothing else changed. Answers are shown against the previous run and
+// the known answers.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use common::bench::format_duration;
+use common::json;
+
+use crate::days::{self, Day};
+
+const DEFAULT_INTERVAL_MS: u64 = 500;
+const INPUTS: &[&str] = &["example", "input"];
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+// Modification times of every file which affects the answers of the day in `dir`, under `root`
+pub fn snapshot(root: &Path, dir: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+
+    ["Cargo.toml", "example", "input"]
+        .iter()
+        .for_each(|name| add_file(&mut files, &dir.join(name)));
+    add_dir(&mut files, &dir.join("src"));
+
+    add_file(&mut files, &root.join("common/Cargo.toml"));
+    add_dir(&mut files, &root.join("common/src"));
+
+    return files;
+}
+
+fn add_file(files: &mut Snapshot, path: &Path) {
+    if let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) {
+        files.insert(path.to_path_buf(), modified);
+    }
+}
+
+fn add_dir(files: &mut Snapshot, dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    entries.flatten().for_each(|entry| match entry.path() {
+        path if path.is_dir() => add_dir(files, &path),
+        path => add_file(files, &path),
+    });
+}
+
+// Files added, removed or modified between two snapshots
+pub fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed.sort();
+
+    return changed;
+}
+
+// The inputs worth re-running for a set of changes: just the changed inputs, unless anything else
+// changed too
+pub fn affected_inputs(dir: &Path, changed: &[PathBuf]) -> Vec<&'static str> {
+    let inputs = changed
+        .iter()
+        .map(|path| INPUTS.iter().find(|name| *path == dir.join(name)).copied())
+        .collect::<Option<Vec<_>>>();
+
+    match inputs {
+        Some(mut inputs) => {
+            inputs.sort();
+            inputs.dedup();
+            inputs
+        }
+        None => INPUTS.to_vec(),
+    }
+}
+
+// One answer as reported by `aoc verify --format json`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Reported {
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+    pub time: Duration,
+    pub status: String,
+    pub expected: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn parse_reported(line: &str) -> Option<Reported> {
+    let mut fields = json::parse_object(line)?;
+    let mut take = |key: &str| fields.remove(key).flatten();
+
+    Some(Reported {
+        part: take("part")?.parse().ok()?,
+        input: take("input")?,
+        answer: take("answer"),
+        time: Duration::from_nanos(take("time_ns")?.parse().ok()?),
+        status: take("status")?,
+        expected: take("expected"),
+        error: take("error"),
+    })
+}
+
+// A line per answer, noting any change since the previous run
+pub fn describe(previous: &BTreeMap<(u8, String), Reported>, current: &[Reported]) -> Vec<String> {
+    current
+        .iter()
+        .map(|r| {
+            let answer = r.answer.as_deref().unwrap_or("-");
+            let status = match (&r.expected, &r.error) {
+                (Some(expected), _) => format!("{}, expected {}", r.status, expected),
+                (_, Some(error)) => format!("{}, {}", r.status, error),
+                _ => r.status.clone(),
+            };
+            let change = match previous.get(&(r.part, r.input.clone())) {
+                None => String::new(),
+                Some(before) if before.answer == r.answer => String::new(),
+                Some(before) => format!("  (was {})", before.answer.as_deref().unwrap_or("-")),
+            };
+
+            format!(
+                "part {} {:<8} {:>16}  {:>8}  {}{}",
+                r.part,
+                r.input,
+                answer,
+                format_duration(r.time),
+                status,
+                change
+            )
+        })
+        .collect()
+}
+
+// Rebuilds and runs verify for the day, through cargo so that code changes are picked up
+fn run(day: &Day, inputs: &[&str]) -> Result<Vec<Reported>, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(days::root())
+        .args(["run", "--quiet", "-p", "runner", "--", "verify"])
+        .arg(day.number.to_string())
+        .args(["--format", "json"]);
+    inputs.iter().for_each(|input| {
+        command.args(["--input", input]);
+    });
+
+    let output = command
+        .output()
+        .map_err(|e| format!("couldn't run cargo: {}", e))?;
+    let reported = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_reported)
+        .collect::<Vec<_>>();
+
+    // verify fails when answers don't match, but then it has still reported them
+    match reported.is_empty() && !output.status.success() {
+        true => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        false => Ok(reported),
+    }
+}
+
+pub fn watch(args: &[String]) -> Result<(), String> {
+    let usage = || String::from("usage: aoc watch <day> [--interval MS]");
+    let (day, interval) = match args {
+        [day] => (day, DEFAULT_INTERVAL_MS),
+        [day, flag, ms] if flag == "--interval" => (
+            day,
+            ms.parse()
+                .map_err(|_| format!("--interval must be a number of milliseconds, got {}", ms))?,
+        ),
+        _ => return Err(usage()),
+    };
+
+    let day = days::find(day)?;
+    let root = days::root();
+    let dir = root.join(day.dir);
+    let mut previous = BTreeMap::new();
+    let mut files = snapshot(&root, &dir);
+    let mut inputs = INPUTS.to_vec();
+
+    println!("watching {}", dir.display());
+
+    loop {
+        match run(day, &inputs) {
+            Err(e) => println!("{}", e),
+            Ok(reported) => {
+                describe(&previous, &reported)
+                    .iter()
+                    .for_each(|line| println!("{}", line));
+                reported.into_iter().for_each(|r| {
+                    previous.insert((r.part, r.input.clone()), r);
+                });
+            }
+        }
+
+        let changed = loop {
+            std::thread::sleep(Duration::from_millis(interval));
+
+            let now = snapshot(&root, &dir);
+            let changed = changes(&files, &now);
+            files = now;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+
+        println!();
+        changed.iter().for_each(|path| {
+            println!(
+                "changed {}",
+                path.strip_prefix(&root).unwrap_or(path).display()
+            )
+        });
+        inputs = affected_inputs(&dir, &changed);
+    }
+}
+
+#[test]
+fn test_changes() {
+    let root = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+    let dir = root.join("day07");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+    std::fs::create_dir_all(root.join("common/src")).unwrap();
+    std::fs::write(dir.join("example"), "1").unwrap();
+    std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+    std::fs::write(dir.join("src/nested/deep.rs"), "").unwrap();
+    std::fs::write(dir.join("notes.txt"), "").unwrap();
+    std::fs::write(root.join("common/src/lib.rs"), "").unwrap();
+
+    let before = snapshot(&root, &dir);
+    assert_eq!(before.len(), 4);
+    assert!(before.contains_key(&root.join("common/src/lib.rs")));
+    assert!(changes(&before, &snapshot(&root, &dir)).is_empty());
+
+    let mut after = before.clone();
+    let later = SystemTime::now() + Duration::from_secs(5);
+    after.insert(dir.join("example"), later);
+    after.insert(dir.join("input"), later);
+    assert_eq!(
+        changes(&before, &after),
+        vec![dir.join("example"), dir.join("input")]
+    );
+
+    after.remove(&dir.join("src/nested/deep.rs"));
+    assert_eq!(changes(&before, &after).len(), 3);
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_affected_inputs() {
+    let dir = Path::new("/aoc/day07");
+
+    assert_eq!(
+        affected_inputs(dir, &[dir.join("input"), dir.join("input")]),
+        vec!["input"]
+    );
+    assert_eq!(
+        affected_inputs(dir, &[dir.join("example"), dir.join("src/lib.rs")]),
+        vec!["example", "input"]
+    );
+}
+
+#[test]
+fn test_parse_reported() {
+    let line = "{\"day\":7,\"part\":2,\"input\":\"example\",\"answer\":\"5906\",\"time_ns\":1500,\"status\":\"regression\",\"expected\":\"59\\\"05\"}";
+
+    assert_eq!(
+        parse_reported(line),
+        Some(Reported {
+            part: 2,
+            input: String::from("example"),
+            answer: Some(String::from("5906")),
+            time: Duration::from_nanos(1500),
+            status: String::from("regression"),
+            expected: Some(String::from("59\"05")),
+            error: None,
+        })
+    );
+
+    let failed = "{\"day\":7,\"part\":1,\"input\":\"input\",\"answer\":null,\"time_ns\":0,\"status\":\"failed\",\"error\":\"a\\nb\\u0001\"}";
+    let failed = parse_reported(failed).unwrap();
+    assert_eq!(failed.answer, None);
+    assert_eq!(failed.error.as_deref(), Some("a\nb\u{1}"));
+
+    assert_eq!(parse_reported("error: could not compile"), None);
+}
+
+#[test]
+fn test_describe() {
+    let reported = |part, answer: &str, status: &str| Reported {
+        part,
+        input: String::from("example"),
+        answer: Some(answer.to_string()),
+        time: Duration::from_micros(12),
+        status: status.to_string(),
+        ..Reported::default()
+    };
+
+    let mut previous = BTreeMap::new();
+    previous.insert((1, String::from("example")), reported(1, "6440", "match"));
+    previous.insert((2, String::from("example")), reported(2, "5905", "match"));
+
+    let current = [
+        reported(1, "6440", "match"),
+        Reported {
+            expected: Some(String::from("5905")),
+            ..reported(2, "5906", "regression")
+        },
+    ];
+
+    assert_eq!(
+        describe(&previous, &current),
+        vec![
+            "part 1 example              6440    12.0µs  match",
+            "part 2 example              5906    12.0µs  regression, expected 5905  (was 5905)",
+        ]
+    );
+}